@@ -17,6 +17,23 @@ fn generate_impl_tuple_calls<W: Write>(mut w: W) -> Result<(), Error> {
         writeln!(w, ");")?;
     }
 
+    for variant in 1..=16 {
+        let prefix = variant - 1;
+        writeln!(
+            w,
+            "impl_tuple_rest!({p}, ParseTuple{v}Error;",
+            p = prefix,
+            v = variant
+        )?;
+
+        for field in 0..prefix {
+            writeln!(w, "\t{f}, T{f}, f{f}, Field{f};", f = field)?;
+        }
+
+        writeln!(w, "\tf{p};", p = prefix)?;
+        writeln!(w, ");")?;
+    }
+
     Ok(())
 }
 
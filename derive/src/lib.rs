@@ -0,0 +1,139 @@
+//! Derive macros for the [token-read](https://docs.rs/token-read/) crate.
+//!
+//! The macros are re-exported by `token-read` when its `derive` feature is enabled
+//! and should be used from there.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input, Data, DeriveInput, Error, Field, Fields, GenericArgument, LitStr,
+    PathArguments, Result, Type,
+};
+
+/// Derives `FromRecord` for a struct with named fields.
+///
+/// Every field is read from the key with the same name,
+/// which can be changed using `#[token(key = "...")]`.
+/// Fields of type [`Option`] may be missing, all other keys are required.
+/// Keys that don't belong to any field are rejected.
+#[proc_macro_derive(FromRecord, attributes(token))]
+pub fn derive_from_record(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    from_record(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn from_record(input: &DeriveInput) -> Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(
+            input,
+            "FromRecord can only be derived for structs",
+        ));
+    };
+
+    let Fields::Named(fields) = &data.fields else {
+        return Err(Error::new_spanned(
+            &data.fields,
+            "FromRecord can only be derived for structs with named fields",
+        ));
+    };
+
+    let mut keys: Vec<LitStr> = Vec::new();
+    let mut values = Vec::new();
+
+    for field in &fields.named {
+        let name = field.ident.as_ref().expect("named fields have names");
+        let key = field_key(field)?;
+
+        if keys.iter().any(|other| other.value() == key.value()) {
+            return Err(Error::new_spanned(
+                field,
+                format!("duplicate key \"{}\"", key.value()),
+            ));
+        }
+
+        let value = match option_inner(&field.ty) {
+            Some(ty) => quote! { record.get_optional::<#ty>(#key) },
+            None => {
+                let ty = &field.ty;
+                quote! { record.get::<#ty>(#key) }
+            }
+        };
+
+        values.push(quote! {
+            #name: #value.map_err(|error| error.map_source(::token_read::ParseFieldError::new))?
+        });
+        keys.push(key);
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::token_read::FromRecord for #ident #ty_generics #where_clause {
+            fn from_record(
+                record: &::token_read::Record,
+            ) -> ::core::result::Result<Self, ::token_read::RecordError<::token_read::ParseFieldError>>
+            {
+                record
+                    .check_keys(&[#(#keys),*])
+                    .map_err(|error| error.map_source(|never| match never {}))?;
+
+                ::core::result::Result::Ok(#ident {
+                    #(#values,)*
+                })
+            }
+        }
+    })
+}
+
+/// Returns the key of a field, which is either given by the `token` attribute or the field name.
+fn field_key(field: &Field) -> Result<LitStr> {
+    let name = field.ident.as_ref().expect("named fields have names");
+    let mut key = None;
+
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("token"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("key") {
+                key = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("unknown token attribute"))
+            }
+        })?;
+    }
+
+    Ok(key.unwrap_or_else(|| {
+        let string = name.to_string();
+        LitStr::new(string.strip_prefix("r#").unwrap_or(&string), name.span())
+    }))
+}
+
+/// Returns `T` if the type is written as `Option<T>`.
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+
+    let segment = path.path.segments.last()?;
+
+    if segment.ident != "Option" {
+        return None;
+    }
+
+    let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+
+    match arguments.args.iter().collect::<Vec<_>>().as_slice() {
+        [GenericArgument::Type(inner)] => Some(inner),
+        _ => None,
+    }
+}
@@ -0,0 +1,221 @@
+/// Generates an asynchronous token reader for a specific `AsyncBufRead` trait.
+///
+/// The `tokio` and `futures` crates define separate, but identical traits,
+/// so each of them gets its own copy of the reader.
+macro_rules! async_reader {
+    ($buf_read:path) => {
+        use std::{
+            future::poll_fn,
+            marker::PhantomData,
+            pin::Pin,
+            task::{ready, Context, Poll},
+        };
+
+        use futures_core::Stream;
+
+        use crate::{
+            source::{owned_line, parse_byte_line, parse_line},
+            FromByteTokens, FromTokens, LineCount, ReadLineError, ReadTokensError,
+        };
+
+        #[cfg(doc)]
+        use crate::TokenReader;
+
+        /// An asynchronous version of [`TokenReader`].
+        ///
+        /// All methods are cancel safe: if a future returned by one of them is dropped before completing,
+        /// the part of the line that was already read is kept and no data is lost.
+        #[derive(Debug)]
+        pub struct AsyncTokenReader<R> {
+            reader: R,
+            buffer: Vec<u8>,
+            line_number: usize,
+        }
+
+        impl<R> AsyncTokenReader<R>
+        where
+            R: $buf_read + Unpin,
+        {
+            /// Creates an [`AsyncTokenReader`] from a buffered asynchronous reader.
+            pub fn new(reader: R) -> Self {
+                AsyncTokenReader {
+                    reader,
+                    buffer: Vec::new(),
+                    line_number: 0,
+                }
+            }
+
+            /// Returns a reference to the underlying reader.
+            pub fn get_ref(&self) -> &R {
+                &self.reader
+            }
+
+            /// Attempts to read the next line and process it using a function, which also receives the line number.
+            ///
+            /// The line terminator is stripped the same way as in [`TokenReader`].
+            fn poll_with_line<T, F>(
+                &mut self,
+                cx: &mut Context<'_>,
+                f: F,
+            ) -> Poll<Result<T, ReadLineError>>
+            where
+                F: FnOnce(&[u8], usize) -> T,
+            {
+                loop {
+                    let available = match ready!(Pin::new(&mut self.reader).poll_fill_buf(cx)) {
+                        Ok(available) => available,
+                        Err(source) => return Poll::Ready(Err(ReadLineError::IoError { source })),
+                    };
+
+                    if available.is_empty() {
+                        if self.buffer.is_empty() {
+                            return Poll::Ready(Err(ReadLineError::EndOfFile));
+                        }
+
+                        self.line_number += 1;
+                        let result = f(&self.buffer, self.line_number);
+
+                        self.buffer.clear();
+                        return Poll::Ready(Ok(result));
+                    }
+
+                    match available.iter().position(|&b| b == b'\n') {
+                        Some(end) if self.buffer.is_empty() => {
+                            let line = &available[..end];
+                            let line = line.strip_suffix(b"\r").unwrap_or(line);
+
+                            self.line_number += 1;
+                            let result = f(line, self.line_number);
+
+                            Pin::new(&mut self.reader).consume(end + 1);
+                            return Poll::Ready(Ok(result));
+                        }
+                        Some(end) => {
+                            self.buffer.extend_from_slice(&available[..end]);
+                            Pin::new(&mut self.reader).consume(end + 1);
+
+                            if self.buffer.ends_with(b"\r") {
+                                self.buffer.pop();
+                            }
+
+                            self.line_number += 1;
+                            let result = f(&self.buffer, self.line_number);
+
+                            self.buffer.clear();
+                            return Poll::Ready(Ok(result));
+                        }
+                        None => {
+                            let length = available.len();
+                            self.buffer.extend_from_slice(available);
+                            Pin::new(&mut self.reader).consume(length);
+                        }
+                    }
+                }
+            }
+
+            /// Reads and parses a single line of whitespace delimited tokens.
+            ///
+            /// See [`TokenReader::line`] for details.
+            pub async fn line<T>(&mut self) -> Result<T, ReadTokensError<T::Error>>
+            where
+                T: FromTokens,
+            {
+                poll_fn(|cx| self.poll_with_line(cx, parse_line)).await?
+            }
+
+            /// Reads a single line, unmodified.
+            ///
+            /// See [`TokenReader::line_raw`] for details.
+            pub async fn line_raw(&mut self) -> Result<String, ReadLineError> {
+                poll_fn(|cx| self.poll_with_line(cx, owned_line)).await?
+            }
+
+            /// Reads and parses a single line of whitespace delimited tokens, without requiring it to be valid UTF-8.
+            ///
+            /// See [`TokenReader::byte_line`] for details.
+            pub async fn byte_line<T>(&mut self) -> Result<T, ReadTokensError<T::Error>>
+            where
+                T: FromByteTokens,
+            {
+                poll_fn(|cx| self.poll_with_line(cx, parse_byte_line)).await?
+            }
+
+            /// Creates a stream that reads and parses a specific number of lines.
+            ///
+            /// See [`TokenReader::take`] for details.
+            pub fn take<T>(&mut self, count: usize) -> Take<'_, T, R, usize>
+            where
+                T: FromTokens,
+            {
+                Take::new(self, count)
+            }
+
+            /// Like [`AsyncTokenReader::take`], but can use non-[`usize`] counts.
+            ///
+            /// See [`TokenReader::take_count`] for details.
+            pub fn take_count<T, S>(&mut self, count: S) -> Take<'_, T, R, S>
+            where
+                T: FromTokens,
+                S: LineCount + Unpin,
+            {
+                Take::new(self, count)
+            }
+        }
+
+        impl<R> From<R> for AsyncTokenReader<R>
+        where
+            R: $buf_read + Unpin,
+        {
+            /// Identical to [`AsyncTokenReader::new`].
+            fn from(value: R) -> Self {
+                AsyncTokenReader::new(value)
+            }
+        }
+
+        /// A stream returned from [`AsyncTokenReader::take`].
+        #[derive(Debug)]
+        pub struct Take<'a, T, R, S> {
+            reader: &'a mut AsyncTokenReader<R>,
+            remaining: S,
+            _phantom: PhantomData<fn() -> T>,
+        }
+
+        impl<'a, T, R, S> Take<'a, T, R, S>
+        where
+            R: $buf_read + Unpin,
+            T: FromTokens,
+            S: LineCount + Unpin,
+        {
+            /// Creates a new [`Take`] stream. It's recommended to use [`AsyncTokenReader::take`] instead.
+            pub fn new(reader: &'a mut AsyncTokenReader<R>, count: S) -> Self {
+                Take {
+                    reader,
+                    remaining: count,
+                    _phantom: PhantomData,
+                }
+            }
+        }
+
+        impl<'a, T, R, S> Stream for Take<'a, T, R, S>
+        where
+            R: $buf_read + Unpin,
+            T: FromTokens,
+            S: LineCount + Unpin,
+        {
+            type Item = Result<T, ReadTokensError<T::Error>>;
+
+            fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+                let this = self.get_mut();
+
+                if this.remaining.empty() {
+                    return Poll::Ready(None);
+                }
+
+                let result = ready!(this.reader.poll_with_line(cx, parse_line));
+                this.remaining.decrement();
+
+                Poll::Ready(Some(result.unwrap_or_else(|error| Err(error.into()))))
+            }
+        }
+    };
+}
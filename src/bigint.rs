@@ -0,0 +1,84 @@
+//! Arbitrary-precision integers and fractions.
+//!
+//! This module is only available with the `bigint` feature.
+//! It re-exports the types of [`num_bigint`] and [`num_rational`], which can be read
+//! like any other value, because they implement [`FromStr`](core::str::FromStr).
+//!
+//! Their parsers are lenient: they accept a leading `+`, leading zeros, underscores between
+//! digits and fractions that aren't in lowest terms, such as `6/8`.
+//! To reject such tokens, wrap the type in [`Canonical`].
+//!
+//! # Example
+//!
+//! ```
+//! # use token_read::{Canonical, ToTokens};
+//! # use token_read::bigint::{BigInt, BigRational, BigUint};
+//! # use anyhow::Result;
+//! #
+//! # fn main() -> Result<()> {
+//! let (a, b, Canonical(c)): (BigUint, BigInt, Canonical<BigRational>) =
+//!     "100000000000000000000 -1 3/4".parse_tokens()?;
+//!
+//! assert_eq!(a.to_string(), "100000000000000000000");
+//! assert_eq!(b, BigInt::from(-1));
+//! assert_eq!(c, BigRational::new(3.into(), 4.into()));
+//! assert!("6/8".parse::<Canonical<BigRational>>().is_err());
+//! #
+//! #   Ok(())
+//! # }
+//! ```
+
+#[cfg(doc)]
+use crate::Canonical;
+
+pub use num_bigint::{BigInt, BigUint, ParseBigIntError};
+pub use num_rational::{BigRational, ParseRatioError, Ratio};
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::ToString, vec::Vec};
+
+    use super::{BigInt, BigRational, BigUint};
+    use crate::{Canonical, ParseCanonicalError, ToTokens};
+
+    #[test]
+    fn parses_large_numbers() {
+        let digits = "9".repeat(1000);
+        let (a, b): (BigUint, BigInt) =
+            alloc::format!("{digits} -{digits}").parse_tokens().unwrap();
+
+        assert_eq!(a.to_string(), digits);
+        assert_eq!(-b, BigInt::from(a));
+    }
+
+    #[test]
+    fn parses_fractions() {
+        let values: Vec<BigRational> = "3/4 -1/2 5".parse_tokens().unwrap();
+
+        assert_eq!(values[0], BigRational::new(3.into(), 4.into()));
+        assert_eq!(values[1], BigRational::new((-1).into(), 2.into()));
+        assert_eq!(values[2], BigRational::from_integer(5.into()));
+        assert!("1/0".parse::<BigRational>().is_err());
+    }
+
+    #[test]
+    fn rejects_non_canonical_tokens() {
+        for token in ["007", "+7", "-0", "1_000"] {
+            assert_eq!(
+                token.parse::<Canonical<BigInt>>(),
+                Err(ParseCanonicalError::NotCanonical),
+                "parsing {token:?}"
+            );
+        }
+
+        for token in ["6/8", "3/1", "1/-2", "0/5"] {
+            assert_eq!(
+                token.parse::<Canonical<BigRational>>(),
+                Err(ParseCanonicalError::NotCanonical),
+                "parsing {token:?}"
+            );
+        }
+
+        assert!("-3/4".parse::<Canonical<BigRational>>().is_ok());
+    }
+}
@@ -0,0 +1,209 @@
+use alloc::{borrow::ToOwned, string::String, vec::Vec};
+use core::{convert::Infallible, str::Utf8Error};
+
+use crate::{swar::find_whitespace, swar::parse_digits, ParseIntBytesError};
+
+#[cfg(doc)]
+use crate::{FromTokens, TokenSource};
+#[cfg(doc)]
+use core::str::FromStr;
+
+/// A trait for types parsable from an iterator of whitespace delimited byte tokens.
+///
+/// This is a counterpart to [`FromTokens`] that doesn't require the input to be valid UTF-8.
+/// Implementations are provided for tuples and collections of types implementing [`FromBytes`].
+pub trait FromByteTokens: Sized {
+    type Error;
+
+    /// Parses a value from an iterator of byte tokens.
+    fn from_byte_tokens<'a, I>(tokens: I) -> Result<Self, Self::Error>
+    where
+        I: Iterator<Item = &'a [u8]>;
+}
+
+/// A trait for types parsable from a single byte token, similar to [`FromStr`].
+///
+/// Implementations are provided for all primitive integers, which are parsed directly from ASCII digits,
+/// as well as for [`Vec<u8>`] and [`String`].
+pub trait FromBytes: Sized {
+    type Err;
+
+    /// Parses a value from a byte token.
+    fn from_bytes(bytes: &[u8]) -> Result<Self, Self::Err>;
+}
+
+/// An iterator over the tokens of a byte slice, separated by ASCII whitespace.
+///
+/// It's used by [`TokenSource::byte_line`].
+/// On ASCII input, it produces the same tokens as [`str::split_whitespace`].
+///
+/// # Example
+///
+/// ```
+/// # use token_read::ByteTokens;
+/// let tokens: Vec<&[u8]> = ByteTokens::new(b" ab\t\xff ").collect();
+/// assert_eq!(tokens, [b"ab".as_slice(), b"\xff".as_slice()]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ByteTokens<'a> {
+    rest: &'a [u8],
+}
+
+impl<'a> ByteTokens<'a> {
+    /// Creates an iterator over the tokens of a byte slice.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        ByteTokens { rest: bytes }
+    }
+}
+
+impl<'a> Iterator for ByteTokens<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = self.rest;
+        self.rest = &[];
+
+        let start = rest.iter().position(|&b| !is_whitespace(b))?;
+        let rest = &rest[start..];
+
+        let end = find_whitespace(rest).unwrap_or(rest.len());
+        let (token, rest) = rest.split_at(end);
+
+        self.rest = rest;
+        Some(token)
+    }
+}
+
+/// Checks whether a byte is an ASCII whitespace character, as defined by [`char::is_whitespace`].
+pub(crate) fn is_whitespace(byte: u8) -> bool {
+    matches!(byte, b' ' | b'\t' | b'\n' | b'\x0b' | b'\x0c' | b'\r')
+}
+
+macro_rules! impl_int {
+    ($($t:ty)*) => {
+        $(
+            impl FromBytes for $t {
+                type Err = ParseIntBytesError;
+
+                /// Parses an integer from ASCII digits with an optional sign.
+                ///
+                /// The accepted format and the returned errors match [`FromStr`].
+                fn from_bytes(bytes: &[u8]) -> Result<Self, Self::Err> {
+                    let (negative, digits) = match bytes {
+                        [] => return Err(ParseIntBytesError::Empty),
+                        [b'+' | b'-'] => return Err(ParseIntBytesError::InvalidDigit),
+                        [b'-', rest @ ..] if <$t>::MIN != 0 => (true, rest),
+                        [b'+', rest @ ..] => (false, rest),
+                        _ => (false, bytes),
+                    };
+
+                    // Numbers with fewer digits than the maximum value can't overflow.
+                    if digits.len() <= (<$t>::MAX.ilog10() as usize).min(19) {
+                        let magnitude = parse_digits(digits).ok_or(ParseIntBytesError::InvalidDigit)?;
+                        let value = magnitude as $t;
+
+                        return Ok(if negative { value.wrapping_neg() } else { value });
+                    }
+
+                    let mut value: $t = 0;
+
+                    for &digit in digits {
+                        let digit = match digit {
+                            b'0'..=b'9' => (digit - b'0') as $t,
+                            _ => return Err(ParseIntBytesError::InvalidDigit),
+                        };
+
+                        value = if negative {
+                            value
+                                .checked_mul(10)
+                                .and_then(|v| v.checked_sub(digit))
+                                .ok_or(ParseIntBytesError::NegOverflow)?
+                        } else {
+                            value
+                                .checked_mul(10)
+                                .and_then(|v| v.checked_add(digit))
+                                .ok_or(ParseIntBytesError::PosOverflow)?
+                        };
+                    }
+
+                    Ok(value)
+                }
+            }
+        )*
+    };
+}
+
+impl_int!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize);
+
+impl FromBytes for Vec<u8> {
+    type Err = Infallible;
+
+    /// Copies the token without any processing.
+    fn from_bytes(bytes: &[u8]) -> Result<Self, Self::Err> {
+        Ok(bytes.to_vec())
+    }
+}
+
+impl FromBytes for String {
+    type Err = Utf8Error;
+
+    /// Copies the token if it's valid UTF-8.
+    fn from_bytes(bytes: &[u8]) -> Result<Self, Self::Err> {
+        core::str::from_utf8(bytes).map(str::to_owned)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ByteTokens, FromBytes, ParseIntBytesError};
+
+    #[test]
+    fn splits_bytes_at_whitespace() {
+        let input = b"\t0 \xff\x0b\r\n 1 ";
+        let tokens: Vec<&[u8]> = ByteTokens::new(input).collect();
+        assert_eq!(tokens, vec![b"0".as_slice(), b"\xff", b"1"]);
+    }
+
+    #[test]
+    fn splits_like_split_whitespace() {
+        for input in ["", " ", "a", " a  b\tc\r\n", "\x0b\x0cx\x0b", "1 2 3"] {
+            let bytes: Vec<&[u8]> = ByteTokens::new(input.as_bytes()).collect();
+            let chars: Vec<&[u8]> = input.split_whitespace().map(str::as_bytes).collect();
+            assert_eq!(bytes, chars, "tokenizing {input:?}");
+        }
+    }
+
+    #[test]
+    fn parses_integers_like_from_str() {
+        let inputs = [
+            "", "+", "-", "0", "-0", "+0", "127", "128", "-128", "-129", "255", "256", "00012",
+            "1x", "x1", "--1", "+-1", " 1", "999x", "-999x",
+        ];
+
+        for input in inputs {
+            let expected_i8 = input.parse::<i8>().map_err(|e| format!("{:?}", e.kind()));
+            let real_i8 = i8::from_bytes(input.as_bytes()).map_err(|e| format!("{e:?}"));
+            assert_eq!(real_i8, expected_i8, "parsing {input:?} as i8");
+
+            let expected_u8 = input.parse::<u8>().map_err(|e| format!("{:?}", e.kind()));
+            let real_u8 = u8::from_bytes(input.as_bytes()).map_err(|e| format!("{e:?}"));
+            assert_eq!(real_u8, expected_u8, "parsing {input:?} as u8");
+        }
+    }
+
+    #[test]
+    fn parses_extreme_values() {
+        assert_eq!(i64::from_bytes(b"-9223372036854775808"), Ok(i64::MIN));
+        assert_eq!(u64::from_bytes(b"18446744073709551615"), Ok(u64::MAX));
+        assert_eq!(
+            u64::from_bytes(b"18446744073709551616"),
+            Err(ParseIntBytesError::PosOverflow)
+        );
+    }
+
+    #[test]
+    fn parses_strings() {
+        assert_eq!(String::from_bytes(b"abc").unwrap(), "abc");
+        assert!(String::from_bytes(b"\xff").is_err());
+    }
+}
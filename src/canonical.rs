@@ -0,0 +1,136 @@
+use core::{
+    fmt::{self, Display, Write},
+    str::FromStr,
+};
+
+use crate::ParseCanonicalError;
+
+/// A value whose token has to be written exactly like the value is displayed.
+///
+/// This rejects tokens that [`FromStr`] accepts but that aren't in canonical form,
+/// such as numbers with leading zeros or a plus sign, or fractions that aren't in lowest terms.
+/// The token is compared with the [`Display`] output of the parsed value without allocating.
+///
+/// # Example
+///
+/// ```
+/// # use token_read::{Canonical, ToTokens};
+/// # use anyhow::Result;
+/// #
+/// # fn main() -> Result<()> {
+/// let (Canonical(a), Canonical(b)): (Canonical<u32>, Canonical<i32>) = "10 -7".parse_tokens()?;
+///
+/// assert_eq!((a, b), (10, -7));
+/// assert!("007".parse::<Canonical<u32>>().is_err());
+/// assert!("+7".parse::<Canonical<i32>>().is_err());
+/// assert!("-0".parse::<Canonical<i32>>().is_err());
+/// #
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Canonical<T>(pub T);
+
+impl<T> Canonical<T> {
+    /// Returns the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: FromStr + Display> FromStr for Canonical<T> {
+    type Err = ParseCanonicalError<T::Err>;
+
+    fn from_str(token: &str) -> Result<Self, Self::Err> {
+        let value: T = token
+            .parse()
+            .map_err(|source| ParseCanonicalError::ParseError { source })?;
+
+        let mut comparator = Comparator { rest: token };
+
+        match write!(comparator, "{value}") {
+            Ok(()) if comparator.rest.is_empty() => Ok(Canonical(value)),
+            _ => Err(ParseCanonicalError::NotCanonical),
+        }
+    }
+}
+
+impl<T: Display> Display for Canonical<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Checks that everything written to it is a prefix of the remaining string, failing otherwise.
+struct Comparator<'a> {
+    rest: &'a str,
+}
+
+impl Write for Comparator<'_> {
+    fn write_str(&mut self, string: &str) -> fmt::Result {
+        self.rest = self.rest.strip_prefix(string).ok_or(fmt::Error)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Canonical, ParseCanonicalError, ToTokens};
+
+    #[test]
+    fn accepts_canonical_tokens() {
+        assert_eq!("0".parse(), Ok(Canonical(0u8)));
+        assert_eq!("-128".parse(), Ok(Canonical(i8::MIN)));
+        assert_eq!("true".parse(), Ok(Canonical(true)));
+    }
+
+    #[test]
+    fn rejects_other_tokens() {
+        for token in ["00", "01", "+1", "-0"] {
+            assert_eq!(
+                token.parse::<Canonical<i32>>(),
+                Err(ParseCanonicalError::NotCanonical),
+                "parsing {token:?}"
+            );
+        }
+
+        assert_eq!(
+            "1.50".parse::<Canonical<f64>>(),
+            Err(ParseCanonicalError::NotCanonical)
+        );
+    }
+
+    #[test]
+    fn rejects_tokens_longer_than_value() {
+        assert_eq!(
+            "1".parse::<Canonical<Displayed>>(),
+            Err(ParseCanonicalError::NotCanonical)
+        );
+    }
+
+    #[test]
+    fn reports_parse_errors() {
+        assert!(matches!(
+            "x".parse_tokens::<(Canonical<u8>,)>(),
+            Err(crate::ParseTokenPatternError::ParseError { .. })
+        ));
+    }
+
+    /// A value that is always displayed as an empty string.
+    #[derive(Debug, PartialEq)]
+    struct Displayed;
+
+    impl core::str::FromStr for Displayed {
+        type Err = ();
+
+        fn from_str(_: &str) -> Result<Self, Self::Err> {
+            Ok(Displayed)
+        }
+    }
+
+    impl core::fmt::Display for Displayed {
+        fn fmt(&self, _: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            Ok(())
+        }
+    }
+}
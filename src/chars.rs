@@ -0,0 +1,269 @@
+use alloc::vec::Vec;
+use core::{convert::Infallible, str::FromStr};
+
+use crate::{AlphabetError, FromBytes, FromTokens, ParseTokenPatternError};
+
+#[cfg(doc)]
+use crate::TokenSource;
+
+/// Implements [`FromTokens`] for types read from a single token.
+macro_rules! impl_single_token {
+    ($($t:ty)*) => {
+        $(
+            impl FromTokens for $t {
+                type Error = ParseTokenPatternError<<$t as FromStr>::Err>;
+
+                fn from_tokens<'a, I>(tokens: I) -> Result<Self, Self::Error>
+                where
+                    I: Iterator<Item = &'a str>,
+                {
+                    let [value] = <[Self; 1]>::from_tokens(tokens)?;
+                    Ok(value)
+                }
+            }
+        )*
+    };
+}
+
+/// A token split into characters, such as a row of a grid.
+///
+/// # Example
+///
+/// ```
+/// # use token_read::{Chars, SliceReader};
+/// # use anyhow::Result;
+/// #
+/// # fn main() -> Result<()> {
+/// let mut input = SliceReader::new("#.#\n..#");
+/// let grid: Vec<Chars> = input.take(2).collect::<Result<_, _>>()?;
+///
+/// assert_eq!(grid[1].0, ['.', '.', '#']);
+/// grid[0].check_alphabet("#.")?;
+/// #
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Chars(pub Vec<char>);
+
+impl Chars {
+    /// Returns the characters.
+    pub fn into_inner(self) -> Vec<char> {
+        self.0
+    }
+
+    /// Checks that all characters are contained in the alphabet.
+    ///
+    /// The position of the first invalid character is counted in characters.
+    pub fn check_alphabet(&self, alphabet: &str) -> Result<(), AlphabetError> {
+        match self.0.iter().position(|&c| !alphabet.contains(c)) {
+            Some(position) => Err(AlphabetError::InvalidCharacter {
+                character: self.0[position],
+                position,
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+impl FromStr for Chars {
+    type Err = Infallible;
+
+    fn from_str(token: &str) -> Result<Self, Self::Err> {
+        Ok(Chars(token.chars().collect()))
+    }
+}
+
+/// A token split into bytes, such as an ASCII string.
+///
+/// It can also be read using [`TokenSource::byte_line`],
+/// in which case the token doesn't have to be valid UTF-8.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bytes(pub Vec<u8>);
+
+impl Bytes {
+    /// Returns the bytes.
+    pub fn into_inner(self) -> Vec<u8> {
+        self.0
+    }
+
+    /// Checks that all bytes are contained in the alphabet.
+    ///
+    /// The position of the first invalid byte is counted in bytes.
+    pub fn check_alphabet(&self, alphabet: &[u8]) -> Result<(), AlphabetError> {
+        match self.0.iter().position(|b| !alphabet.contains(b)) {
+            Some(position) => Err(AlphabetError::InvalidByte {
+                byte: self.0[position],
+                position,
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+impl FromStr for Bytes {
+    type Err = Infallible;
+
+    fn from_str(token: &str) -> Result<Self, Self::Err> {
+        Ok(Bytes(token.as_bytes().to_vec()))
+    }
+}
+
+impl FromBytes for Bytes {
+    type Err = Infallible;
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, Self::Err> {
+        Ok(Bytes(bytes.to_vec()))
+    }
+}
+
+/// A token of zeros and ones, such as `10110`.
+///
+/// The bits are stored in the order they were written in.
+///
+/// # Example
+///
+/// ```
+/// # use token_read::{BitString, ToTokens};
+/// # use anyhow::Result;
+/// #
+/// # fn main() -> Result<()> {
+/// let (mask,): (BitString,) = "1011".parse_tokens()?;
+///
+/// assert_eq!(mask.0, [true, false, true, true]);
+/// assert_eq!(mask.packed(), [0b1101]);
+/// assert!("10a1".parse::<BitString>().is_err());
+/// #
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BitString(pub Vec<bool>);
+
+impl BitString {
+    /// Returns the bits.
+    pub fn into_inner(self) -> Vec<bool> {
+        self.0
+    }
+
+    /// Packs the bits into 64-bit words.
+    ///
+    /// Bit `i` of the string is stored in bit `i % 64` of word `i / 64`.
+    pub fn packed(&self) -> Vec<u64> {
+        self.0
+            .chunks(64)
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .enumerate()
+                    .fold(0, |word, (i, &bit)| word | (u64::from(bit) << i))
+            })
+            .collect()
+    }
+}
+
+impl FromStr for BitString {
+    type Err = AlphabetError;
+
+    fn from_str(token: &str) -> Result<Self, Self::Err> {
+        token
+            .chars()
+            .enumerate()
+            .map(|(position, character)| match character {
+                '0' => Ok(false),
+                '1' => Ok(true),
+                _ => Err(AlphabetError::InvalidCharacter {
+                    character,
+                    position,
+                }),
+            })
+            .collect::<Result<_, _>>()
+            .map(BitString)
+    }
+}
+
+impl FromBytes for BitString {
+    type Err = AlphabetError;
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, Self::Err> {
+        bytes
+            .iter()
+            .enumerate()
+            .map(|(position, &byte)| match byte {
+                b'0' => Ok(false),
+                b'1' => Ok(true),
+                _ => Err(AlphabetError::InvalidByte { byte, position }),
+            })
+            .collect::<Result<_, _>>()
+            .map(BitString)
+    }
+}
+
+impl_single_token!(Chars Bytes BitString);
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+
+    use crate::{AlphabetError, BitString, Bytes, Chars, FromBytes, ToTokens};
+
+    #[test]
+    fn splits_tokens() {
+        let (chars, bytes): (Chars, Bytes) = "žab ab".parse_tokens().unwrap();
+
+        assert_eq!(chars.0, ['ž', 'a', 'b']);
+        assert_eq!(bytes.0, b"ab");
+        assert_eq!("xy".parse_tokens::<Chars>().unwrap().0, ['x', 'y']);
+    }
+
+    #[test]
+    fn reports_characters_outside_alphabet() {
+        let chars: Chars = "ž.x".parse_tokens().unwrap();
+
+        assert_eq!(
+            chars.check_alphabet("ž."),
+            Err(AlphabetError::InvalidCharacter {
+                character: 'x',
+                position: 2
+            })
+        );
+        assert_eq!(
+            Bytes(b"01a".to_vec()).check_alphabet(b"01"),
+            Err(AlphabetError::InvalidByte {
+                byte: b'a',
+                position: 2
+            })
+        );
+    }
+
+    #[test]
+    fn parses_bit_strings() {
+        assert_eq!("".parse(), Ok(BitString(Vec::new())));
+        assert_eq!("01".parse(), Ok(BitString(vec![false, true])));
+        assert_eq!(
+            "0ž1".parse::<BitString>(),
+            Err(AlphabetError::InvalidCharacter {
+                character: 'ž',
+                position: 1
+            })
+        );
+        assert_eq!(
+            BitString::from_bytes(b"1\xff"),
+            Err(AlphabetError::InvalidByte {
+                byte: 0xff,
+                position: 1
+            })
+        );
+    }
+
+    #[test]
+    fn packs_bits() {
+        let mut bits = vec![false; 65];
+        bits[0] = true;
+        bits[63] = true;
+        bits[64] = true;
+
+        assert_eq!(BitString(bits).packed(), [1 | 1 << 63, 1]);
+        assert!(BitString::default().packed().is_empty());
+    }
+}
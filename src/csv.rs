@@ -0,0 +1,553 @@
+use alloc::{
+    borrow::{Cow, ToOwned},
+    string::String,
+    vec::Vec,
+};
+use core::{convert::Infallible, marker::PhantomData};
+
+use thiserror::Error;
+
+use crate::{source::check_utf8, FromTokens, ReadLineError, Remainder, TokenSource};
+
+#[cfg(doc)]
+use crate::{ReaderOptions, RestOfLine};
+
+/// An error returned when reading a row of a CSV file.
+///
+/// Rows are numbered by their line in the input and columns are numbered from one.
+#[derive(Error, Debug)]
+pub enum CsvError<E> {
+    #[error("failed to read row")]
+    ReadError { source: ReadLineError },
+    #[error("invalid quoting in column {column} on row {row}")]
+    InvalidQuote { row: usize, column: usize },
+    #[error("got {real} fields on row {row}, expected {expected} fields from the header")]
+    FieldCount {
+        row: usize,
+        real: usize,
+        expected: usize,
+    },
+    #[error("no header was read")]
+    MissingHeader,
+    #[error("failed to parse column {column} on row {row}")]
+    ParseError {
+        source: E,
+        row: usize,
+        column: usize,
+    },
+}
+
+impl<E> From<ReadLineError> for CsvError<E> {
+    fn from(source: ReadLineError) -> Self {
+        CsvError::ReadError { source }
+    }
+}
+
+/// A reader for comma or tab separated values, built on top of a [`TokenSource`].
+///
+/// Each line is a single row. Fields are separated by the delimiter and may be enclosed in double quotes,
+/// in which case they can contain the delimiter, and quotes are escaped by doubling them.
+/// Rows are parsed from their fields using [`FromTokens`], the same way as lines of tokens.
+/// Fields are not trimmed and an empty line contains no fields.
+/// A [`RestOfLine`] gets the rest of the row unmodified, including delimiters and quotes.
+///
+/// Errors caused by a field contain its column.
+/// If the error came from [`FromTokens`], it's the column of the last field that was read.
+///
+/// # Example
+///
+/// ```
+/// # use token_read::{CsvReader, SliceReader};
+/// # use anyhow::Result;
+/// #
+/// # fn main() -> Result<()> {
+/// let input = SliceReader::new("name,age\n\"Smith, Alice\",42\nBob,37");
+/// let mut csv = CsvReader::new(input);
+///
+/// let header = csv.read_header()?;
+/// assert_eq!(header, ["name", "age"]);
+///
+/// let people: Vec<(String, u8)> = csv.rows().collect::<Result<_, _>>()?;
+/// assert_eq!(people[0], ("Smith, Alice".to_owned(), 42));
+/// #
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct CsvReader<S> {
+    input: S,
+    delimiter: char,
+    header: Option<Vec<String>>,
+}
+
+impl<S: TokenSource> CsvReader<S> {
+    /// Creates a [`CsvReader`] for comma separated values.
+    ///
+    /// Options set on the underlying reader, like [`ReaderOptions`], still apply.
+    pub fn new(input: S) -> Self {
+        CsvReader {
+            input,
+            delimiter: ',',
+            header: None,
+        }
+    }
+
+    /// Creates a [`CsvReader`] for tab separated values.
+    pub fn tsv(input: S) -> Self {
+        CsvReader::new(input).delimiter('\t')
+    }
+
+    /// Sets the character separating fields.
+    pub fn delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Returns the header, if it was read using [`CsvReader::read_header`].
+    pub fn header(&self) -> Option<&[String]> {
+        self.header.as_deref()
+    }
+
+    /// Consumes the reader, returning the underlying [`TokenSource`].
+    pub fn into_inner(self) -> S {
+        self.input
+    }
+
+    /// Reads the next row as the header, which contains the names of the columns.
+    pub fn read_header(&mut self) -> Result<&[String], CsvError<Infallible>> {
+        let header = self.with_fields(|fields, _, _| {
+            Ok(fields
+                .iter()
+                .map(|field| field.value.as_ref().to_owned())
+                .collect())
+        })?;
+
+        Ok(self.header.insert(header))
+    }
+
+    /// Reads and parses a single row.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use token_read::{CsvReader, SliceReader};
+    /// # use anyhow::Result;
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let mut csv = CsvReader::tsv(SliceReader::new("1\t\"a\"\"b\"\t2"));
+    /// let (a, b, c): (u8, String, u8) = csv.row()?;
+    ///
+    /// assert_eq!(b, "a\"b");
+    /// #
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn row<T>(&mut self) -> Result<T, CsvError<T::Error>>
+    where
+        T: FromTokens,
+    {
+        self.with_fields(|fields, row, _| {
+            let mut tokens = Fields {
+                fields: fields.iter(),
+                position: 0,
+            };
+
+            T::from_line_tokens(&mut tokens).map_err(|source| CsvError::ParseError {
+                source,
+                row,
+                column: tokens.position,
+            })
+        })
+    }
+
+    /// Creates an iterator that reads and parses rows until the end of the input.
+    pub fn rows<T>(&mut self) -> CsvRows<'_, T, S>
+    where
+        T: FromTokens,
+    {
+        CsvRows {
+            reader: self,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Reads the next line, splits it into fields and processes them using a function,
+    /// which also receives the row number and the header.
+    fn with_fields<T, E, F>(&mut self, f: F) -> Result<T, CsvError<E>>
+    where
+        F: FnOnce(&[Field<'_>], usize, Option<&[String]>) -> Result<T, CsvError<E>>,
+    {
+        let delimiter = self.delimiter;
+        let header = self.header.as_deref();
+
+        self.input.with_line(|line, row| {
+            let line = check_utf8(line, row)?;
+            let fields = split_fields(line, delimiter)
+                .map_err(|column| CsvError::InvalidQuote { row, column })?;
+
+            f(&fields, row, header)
+        })?
+    }
+}
+
+/// A field of a row, together with the rest of the line starting at it.
+struct Field<'a> {
+    value: Cow<'a, str>,
+    rest: &'a str,
+}
+
+/// An iterator over the fields of a row, which remembers the column of the last field that was read.
+struct Fields<'a, 'b> {
+    fields: core::slice::Iter<'a, Field<'b>>,
+    position: usize,
+}
+
+impl<'a> Iterator for Fields<'a, '_> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.position += 1;
+        self.fields.next().map(|field| field.value.as_ref())
+    }
+}
+
+impl<'a, 'b: 'a> Remainder<'a> for Fields<'a, 'b> {
+    /// Returns the unparsed text of the remaining fields, keeping their delimiters and quotes.
+    fn remainder(&mut self) -> &'a str {
+        let rest = self
+            .fields
+            .as_slice()
+            .first()
+            .map_or("", |field| field.rest);
+
+        self.fields = Default::default();
+        rest.trim()
+    }
+}
+
+/// Splits a line into fields, returning the column of the first invalid field on error.
+fn split_fields(line: &str, delimiter: char) -> Result<Vec<Field<'_>>, usize> {
+    let mut fields = Vec::new();
+
+    if line.is_empty() {
+        return Ok(fields);
+    }
+
+    let mut rest = line;
+
+    loop {
+        let column = fields.len() + 1;
+        let start = rest;
+
+        if let Some(quoted) = rest.strip_prefix('"') {
+            let mut value = String::new();
+            let mut quoted = quoted;
+
+            loop {
+                let end = quoted.find('"').ok_or(column)?;
+                value.push_str(&quoted[..end]);
+                quoted = &quoted[end + 1..];
+
+                match quoted.strip_prefix('"') {
+                    Some(after) => {
+                        value.push('"');
+                        quoted = after;
+                    }
+                    None => break,
+                }
+            }
+
+            fields.push(Field {
+                value: Cow::Owned(value),
+                rest: start,
+            });
+
+            if quoted.is_empty() {
+                return Ok(fields);
+            }
+
+            rest = quoted.strip_prefix(delimiter).ok_or(column)?;
+        } else {
+            match rest.find(delimiter) {
+                Some(end) => {
+                    fields.push(Field {
+                        value: Cow::Borrowed(&rest[..end]),
+                        rest: start,
+                    });
+                    rest = &rest[end + delimiter.len_utf8()..];
+                }
+                None => {
+                    fields.push(Field {
+                        value: Cow::Borrowed(rest),
+                        rest: start,
+                    });
+                    return Ok(fields);
+                }
+            }
+        }
+    }
+}
+
+/// An iterator returned from [`CsvReader::rows`].
+#[derive(Debug)]
+pub struct CsvRows<'a, T, S> {
+    reader: &'a mut CsvReader<S>,
+    _phantom: PhantomData<T>,
+}
+
+impl<T, S> Iterator for CsvRows<'_, T, S>
+where
+    S: TokenSource,
+    T: FromTokens,
+{
+    type Item = Result<T, CsvError<T::Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.reader.row() {
+            Err(CsvError::ReadError {
+                source: ReadLineError::EndOfFile,
+            }) => None,
+            result => Some(result),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+mod record {
+    use alloc::string::String;
+
+    use serde::de::DeserializeOwned;
+
+    use super::{CsvError, CsvReader};
+    use crate::{de::NamedFields, DeserializeError, TokenSource};
+
+    impl<S: TokenSource> CsvReader<S> {
+        /// Deserializes a row using [serde](https://serde.rs), matching fields to the columns of the header.
+        ///
+        /// The header has to be read using [`CsvReader::read_header`] first.
+        /// Empty fields are deserialized as [`None`] if the type is an [`Option`].
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// # use token_read::{CsvReader, SliceReader};
+        /// # use serde::Deserialize;
+        /// # use anyhow::Result;
+        /// #
+        /// #[derive(Deserialize)]
+        /// struct City {
+        ///     name: String,
+        ///     population: u64,
+        ///     mayor: Option<String>,
+        /// }
+        ///
+        /// # fn main() -> Result<()> {
+        /// let input = SliceReader::new("population,name,mayor\n1309000,Prague,");
+        /// let mut csv = CsvReader::new(input);
+        /// csv.read_header()?;
+        ///
+        /// let city: City = csv.record()?;
+        ///
+        /// assert_eq!(city.name, "Prague");
+        /// assert_eq!(city.population, 1309000);
+        /// assert_eq!(city.mayor, None);
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        pub fn record<T>(&mut self) -> Result<T, CsvError<DeserializeError>>
+        where
+            T: DeserializeOwned,
+        {
+            if self.header.is_none() {
+                return Err(CsvError::MissingHeader);
+            }
+
+            self.with_fields(|fields, row, header| {
+                let header = header.unwrap_or_default();
+
+                if fields.len() != header.len() {
+                    return Err(CsvError::FieldCount {
+                        row,
+                        real: fields.len(),
+                        expected: header.len(),
+                    });
+                }
+
+                let fields = header
+                    .iter()
+                    .map(String::as_str)
+                    .zip(fields.iter().map(|field| field.value.as_ref()));
+                let mut access = NamedFields::new(fields);
+
+                T::deserialize(&mut access).map_err(|source| CsvError::ParseError {
+                    source,
+                    row,
+                    column: access.position(),
+                })
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{borrow::ToOwned, string::String, vec, vec::Vec};
+
+    use alloc::borrow::Cow;
+
+    use super::split_fields;
+    use crate::{CsvError, CsvReader, ParseTokenPatternError, RestOfLine, SliceReader};
+
+    fn split_values(line: &str, delimiter: char) -> Result<Vec<Cow<'_, str>>, usize> {
+        split_fields(line, delimiter)
+            .map(|fields| fields.into_iter().map(|field| field.value).collect())
+    }
+
+    #[test]
+    fn splits_plain_fields() {
+        assert_eq!(
+            split_values("a,b,,c", ','),
+            Ok(vec!["a".into(), "b".into(), "".into(), "c".into()])
+        );
+        assert_eq!(split_values("", ','), Ok(vec![]));
+    }
+
+    #[test]
+    fn splits_quoted_fields() {
+        assert_eq!(
+            split_values("\"a,b\",\"\"\"\",c", ','),
+            Ok(vec!["a,b".into(), "\"".into(), "c".into()])
+        );
+        assert_eq!(split_values("\"\"", ','), Ok(vec!["".into()]));
+    }
+
+    #[test]
+    fn rejects_invalid_quotes() {
+        assert_eq!(split_values("a,\"b", ','), Err(2));
+        assert_eq!(split_values("\"a\"b,c", ','), Err(1));
+    }
+
+    #[test]
+    fn reads_rows() {
+        let mut csv = CsvReader::tsv(SliceReader::new("1\tx y\n2\t\"z\"\n"));
+        let rows: Vec<(u8, String)> = csv.rows().collect::<Result<_, _>>().unwrap();
+
+        assert_eq!(rows, vec![(1, "x y".to_owned()), (2, "z".to_owned())]);
+    }
+
+    #[test]
+    fn keeps_rest_of_row_unmodified() {
+        let mut csv = CsvReader::new(SliceReader::new("1,\"a, b\",c\n2,x"));
+
+        let (id, rest): (u8, RestOfLine) = csv.row().unwrap();
+        assert_eq!((id, rest.as_str()), (1, "\"a, b\",c"));
+
+        let (id, name, rest): (u8, String, RestOfLine) = csv.row().unwrap();
+        assert_eq!((id, name.as_str(), rest.as_str()), (2, "x", ""));
+    }
+
+    #[test]
+    fn reads_header() {
+        let mut csv = CsvReader::new(SliceReader::new("a,\"b\"\n1,2"));
+        csv.read_header().unwrap();
+
+        assert_eq!(csv.header(), Some(&["a".to_owned(), "b".to_owned()][..]));
+        assert_eq!(csv.row::<Vec<u8>>().unwrap(), vec![1, 2]);
+    }
+
+    #[test]
+    fn reports_row_and_column() {
+        let mut csv = CsvReader::new(SliceReader::new("1,2\n3,x,5\n\"6"));
+        csv.row::<Vec<u8>>().unwrap();
+
+        assert!(matches!(
+            csv.row::<Vec<u8>>(),
+            Err(CsvError::ParseError {
+                row: 2,
+                column: 2,
+                ..
+            })
+        ));
+        assert!(matches!(
+            csv.row::<Vec<u8>>(),
+            Err(CsvError::InvalidQuote { row: 3, column: 1 })
+        ));
+    }
+
+    #[test]
+    fn reports_column_of_missing_field() {
+        let mut csv = CsvReader::new(SliceReader::new("1,2"));
+
+        assert!(matches!(
+            csv.row::<(u8, u8, u8)>(),
+            Err(CsvError::ParseError {
+                row: 1,
+                column: 3,
+                source: ParseTokenPatternError::TooFewTokens { .. },
+            })
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    mod record {
+        use alloc::{borrow::ToOwned, string::String};
+
+        use serde::Deserialize;
+
+        use crate::{CsvError, CsvReader, SliceReader};
+
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct Person {
+            name: String,
+            age: u8,
+            email: Option<String>,
+        }
+
+        #[test]
+        fn reads_records_by_header() {
+            let mut csv = CsvReader::new(SliceReader::new("age,email,name\n42,,\"Smith, A.\""));
+            csv.read_header().unwrap();
+
+            let person: Person = csv.record().unwrap();
+
+            assert_eq!(
+                person,
+                Person {
+                    name: "Smith, A.".to_owned(),
+                    age: 42,
+                    email: None,
+                }
+            );
+        }
+
+        #[test]
+        fn reports_record_errors() {
+            let mut csv = CsvReader::new(SliceReader::new("name,age,email\nA,x,\nB"));
+
+            assert!(matches!(
+                csv.record::<Person>(),
+                Err(CsvError::MissingHeader)
+            ));
+
+            csv.read_header().unwrap();
+
+            assert!(matches!(
+                csv.record::<Person>(),
+                Err(CsvError::ParseError {
+                    row: 2,
+                    column: 2,
+                    ..
+                })
+            ));
+            assert!(matches!(
+                csv.record::<Person>(),
+                Err(CsvError::FieldCount {
+                    row: 3,
+                    real: 1,
+                    expected: 3
+                })
+            ));
+        }
+    }
+}
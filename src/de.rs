@@ -0,0 +1,869 @@
+use alloc::{
+    borrow::ToOwned,
+    string::{String, ToString},
+};
+use core::fmt::Display;
+
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess,
+    VariantAccess, Visitor,
+};
+use thiserror::Error;
+
+use crate::{length_prefixed, ParseToken, ReadLineError, SliceReader, TokenSource};
+
+/// An error returned when deserializing a value from whitespace delimited tokens.
+#[derive(Error, Debug)]
+pub enum DeserializeError {
+    #[error("failed to read line")]
+    ReadError { source: ReadLineError },
+    #[error("failed to parse token \"{token}\": {message}")]
+    ParseError { token: String, message: String },
+    #[error("unexpected token \"{token}\" after the end of the value")]
+    TrailingTokens { token: String },
+    #[error("deserializing {kind} is not supported")]
+    Unsupported { kind: &'static str },
+    #[error("{message}")]
+    Custom { message: String },
+}
+
+impl From<ReadLineError> for DeserializeError {
+    fn from(source: ReadLineError) -> Self {
+        DeserializeError::ReadError { source }
+    }
+}
+
+impl de::Error for DeserializeError {
+    fn custom<T: Display>(message: T) -> Self {
+        DeserializeError::Custom {
+            message: message.to_string(),
+        }
+    }
+}
+
+/// A [serde](https://serde.rs) deserializer that reads values from whitespace delimited tokens.
+///
+/// Values are mapped to tokens like this:
+///
+/// * Primitives and strings are read from a single token.
+/// * Structs, tuples and tuple structs are read from consecutive tokens, which may span multiple lines.
+/// * Sequences take the rest of the current line, or the next line if the current one has been fully read.
+///   Sequences prefixed by their length can be read using [`length_prefixed`].
+/// * Maps take the rest of the line as alternating keys and values.
+/// * Enums are read from a token containing the name of the variant, followed by its fields.
+/// * Options are [`None`] if there are no more tokens on the current line.
+///
+/// Self-describing types, like `#[serde(untagged)]` enums, aren't supported.
+///
+/// It's usually used through [`from_str`] or [`TokenSource::deserialize`].
+#[derive(Debug)]
+pub struct Deserializer<S> {
+    input: S,
+    line: Option<String>,
+    position: usize,
+}
+
+impl<S: TokenSource> Deserializer<S> {
+    /// Creates a [`Deserializer`] reading from a [`TokenSource`].
+    pub fn new(input: S) -> Self {
+        Deserializer {
+            input,
+            line: None,
+            position: 0,
+        }
+    }
+
+    /// Checks that there are no more tokens on the current line.
+    pub fn end_line(&mut self) -> Result<(), DeserializeError> {
+        match self.peek_token() {
+            Some(token) => Err(DeserializeError::TrailingTokens {
+                token: token.to_owned(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks that there are no more tokens in the whole input.
+    pub fn end(&mut self) -> Result<(), DeserializeError> {
+        loop {
+            self.end_line()?;
+
+            match self.next_line() {
+                Ok(()) => {}
+                Err(DeserializeError::ReadError {
+                    source: ReadLineError::EndOfFile,
+                }) => return Ok(()),
+                Err(error) => return Err(error),
+            }
+        }
+    }
+
+    /// Returns the unread part of the current line.
+    fn rest(&self) -> &str {
+        self.line
+            .as_deref()
+            .map_or("", |line| &line[self.position..])
+    }
+
+    /// Returns the next token on the current line without consuming it.
+    fn peek_token(&self) -> Option<&str> {
+        self.rest().split_whitespace().next()
+    }
+
+    /// Reads the next line from the input.
+    fn next_line(&mut self) -> Result<(), DeserializeError> {
+        self.line = Some(self.input.line_raw()?);
+        self.position = 0;
+        Ok(())
+    }
+
+    /// Moves to the next line if the current one has no tokens left or if no line was read yet.
+    fn start_line(&mut self) -> Result<(), DeserializeError> {
+        if self.peek_token().is_none() {
+            self.next_line()?;
+        }
+
+        Ok(())
+    }
+
+    /// Consumes the next token, reading new lines if necessary.
+    fn next_token(&mut self) -> Result<&str, DeserializeError> {
+        while self.peek_token().is_none() {
+            self.next_line()?;
+        }
+
+        let line = self.line.as_deref().unwrap_or_default();
+        let rest = &line[self.position..];
+        let trimmed = rest.trim_start();
+        let length = trimmed.find(char::is_whitespace).unwrap_or(trimmed.len());
+        let start = self.position + (rest.len() - trimmed.len());
+
+        self.position = start + length;
+        Ok(&line[start..start + length])
+    }
+
+    /// Consumes the next token and parses it.
+    fn parse<T>(&mut self) -> Result<T, DeserializeError>
+    where
+        T: ParseToken,
+        T::Err: Display,
+    {
+        let token = self.next_token()?;
+
+        T::parse_token(token).map_err(|error| DeserializeError::ParseError {
+            token: token.to_owned(),
+            message: error.to_string(),
+        })
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident),* $(,)?) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                visitor.$visit(self.parse()?)
+            }
+        )*
+    };
+}
+
+impl<'de, S: TokenSource> de::Deserializer<'de> for &mut Deserializer<S> {
+    type Error = DeserializeError;
+
+    fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(DeserializeError::Unsupported {
+            kind: "self-describing types",
+        })
+    }
+
+    deserialize_parsed! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_str(self.next_token()?)
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_bytes(self.next_token()?.as_bytes())
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if self.line.is_none() {
+            self.next_line()?;
+        }
+
+        if self.peek_token().is_some() {
+            visitor.visit_some(self)
+        } else {
+            visitor.visit_none()
+        }
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if name == length_prefixed::NAME {
+            visitor.visit_newtype_struct(LengthPrefixed { de: self })
+        } else {
+            visitor.visit_newtype_struct(self)
+        }
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.start_line()?;
+        visitor.visit_seq(RestOfLine { de: self })
+    }
+
+    fn deserialize_tuple<V>(self, length: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(Counted::new(self, length))
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        length: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(Counted::new(self, length))
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.start_line()?;
+        visitor.visit_map(RestOfLine { de: self })
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(Counted::new(self, fields.len()))
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_enum(self)
+    }
+
+    fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.next_token()?;
+        visitor.visit_unit()
+    }
+
+    fn is_human_readable(&self) -> bool {
+        true
+    }
+}
+
+/// Deserializes a sequence prefixed by its length, see [`length_prefixed`].
+///
+/// Only sequences, possibly wrapped in newtype structs, can be prefixed by their length.
+struct LengthPrefixed<'a, S> {
+    de: &'a mut Deserializer<S>,
+}
+
+impl<'de, S: TokenSource> de::Deserializer<'de> for LengthPrefixed<'_, S> {
+    type Error = DeserializeError;
+
+    fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(DeserializeError::Unsupported {
+            kind: "length-prefixed values other than sequences",
+        })
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let length = self.de.parse()?;
+        visitor.visit_seq(Counted::new(self.de, length))
+    }
+
+    fn is_human_readable(&self) -> bool {
+        true
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+/// Provides a fixed number of consecutive values, used for tuples, structs and length-prefixed sequences.
+struct Counted<'a, S> {
+    de: &'a mut Deserializer<S>,
+    remaining: usize,
+}
+
+impl<'a, S> Counted<'a, S> {
+    fn new(de: &'a mut Deserializer<S>, remaining: usize) -> Self {
+        Counted { de, remaining }
+    }
+}
+
+impl<'de, S: TokenSource> SeqAccess<'de> for Counted<'_, S> {
+    type Error = DeserializeError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+
+        self.remaining -= 1;
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+/// Provides values until the end of the current line.
+struct RestOfLine<'a, S> {
+    de: &'a mut Deserializer<S>,
+}
+
+impl<'de, S: TokenSource> SeqAccess<'de> for RestOfLine<'_, S> {
+    type Error = DeserializeError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        if self.de.peek_token().is_none() {
+            return Ok(None);
+        }
+
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+}
+
+impl<'de, S: TokenSource> MapAccess<'de> for RestOfLine<'_, S> {
+    type Error = DeserializeError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        self.next_element_seed(seed)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        seed.deserialize(&mut *self.de)
+    }
+}
+
+impl<'de, S: TokenSource> EnumAccess<'de> for &mut Deserializer<S> {
+    type Error = DeserializeError;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let tag: de::value::StrDeserializer<'_, DeserializeError> =
+            self.next_token()?.into_deserializer();
+        let variant = seed.deserialize(tag)?;
+
+        Ok((variant, self))
+    }
+}
+
+impl<'de, S: TokenSource> VariantAccess<'de> for &mut Deserializer<S> {
+    type Error = DeserializeError;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V>(self, length: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(Counted::new(self, length))
+    }
+
+    fn struct_variant<V>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(Counted::new(self, fields.len()))
+    }
+}
+
+/// Deserializes a value from a string of whitespace delimited tokens.
+///
+/// The whole string must be used, see [`Deserializer`] for details on how values are mapped to tokens.
+///
+/// # Example
+///
+/// ```
+/// # use serde::Deserialize;
+/// # use anyhow::Result;
+/// #
+/// #[derive(Deserialize, Debug, PartialEq)]
+/// struct Player {
+///     name: String,
+///     points: u64,
+/// }
+///
+/// # fn main() -> Result<()> {
+/// let players: (u8, Player, Player) = token_read::from_str("2\nAlice 30\nBob 25")?;
+///
+/// assert_eq!(players.2, Player { name: "Bob".to_owned(), points: 25 });
+/// #
+/// #   Ok(())
+/// # }
+/// ```
+pub fn from_str<T>(input: &str) -> Result<T, DeserializeError>
+where
+    T: DeserializeOwned,
+{
+    let mut de = Deserializer::new(SliceReader::new(input));
+    let value = T::deserialize(&mut de)?;
+
+    de.end()?;
+    Ok(value)
+}
+
+/// Provides named fields, like the columns of a CSV row, as a map from names to values.
+///
+/// It remembers the position of the last field that was read, starting from one.
+pub(crate) struct NamedFields<'a, I> {
+    fields: I,
+    value: Option<&'a str>,
+    position: usize,
+}
+
+impl<'a, I> NamedFields<'a, I>
+where
+    I: Iterator<Item = (&'a str, &'a str)>,
+{
+    /// Creates a map from pairs of names and values.
+    pub(crate) fn new(fields: I) -> Self {
+        NamedFields {
+            fields,
+            value: None,
+            position: 0,
+        }
+    }
+
+    /// Returns the position of the last field that was read.
+    pub(crate) fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl<'a, 'de, I> de::Deserializer<'de> for &mut NamedFields<'a, I>
+where
+    I: Iterator<Item = (&'a str, &'a str)>,
+{
+    type Error = DeserializeError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_map(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+impl<'a, 'de, I> MapAccess<'de> for NamedFields<'a, I>
+where
+    I: Iterator<Item = (&'a str, &'a str)>,
+{
+    type Error = DeserializeError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        let Some((name, value)) = self.fields.next() else {
+            return Ok(None);
+        };
+
+        self.value = Some(value);
+        self.position += 1;
+
+        let name: de::value::StrDeserializer<'_, DeserializeError> = name.into_deserializer();
+        seed.deserialize(name).map(Some)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let value = self.value.take().unwrap_or_default();
+        seed.deserialize(FieldDeserializer(value))
+    }
+}
+
+/// Deserializes a value from a single field, which may contain whitespace.
+///
+/// Empty fields are deserialized as [`None`] if the type is an [`Option`].
+struct FieldDeserializer<'a>(&'a str);
+
+impl FieldDeserializer<'_> {
+    /// Parses the field.
+    fn parse<T>(&self) -> Result<T, DeserializeError>
+    where
+        T: ParseToken,
+        T::Err: Display,
+    {
+        T::parse_token(self.0).map_err(|error| DeserializeError::ParseError {
+            token: self.0.to_owned(),
+            message: error.to_string(),
+        })
+    }
+}
+
+impl<'de> de::Deserializer<'de> for FieldDeserializer<'_> {
+    type Error = DeserializeError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_str(self.0)
+    }
+
+    deserialize_parsed! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if self.0.is_empty() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let variant: de::value::StrDeserializer<'_, DeserializeError> = self.0.into_deserializer();
+        visitor.visit_enum(variant)
+    }
+
+    serde::forward_to_deserialize_any! {
+        str string bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+/// Deserializes a value which ends at the end of a line, see [`TokenSource::deserialize`].
+pub(crate) fn deserialize_lines<S, T>(input: &mut S) -> Result<T, DeserializeError>
+where
+    S: TokenSource,
+    T: DeserializeOwned,
+{
+    let mut de = Deserializer::new(input);
+    let value = T::deserialize(&mut de)?;
+
+    de.end_line()?;
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use serde::Deserialize;
+
+    use crate::{
+        from_str, length_prefixed, DeserializeError, ReadLineError, TokenReader, TokenSource,
+    };
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Polygon {
+        name: String,
+        #[serde(deserialize_with = "length_prefixed::deserialize")]
+        points: Vec<Point>,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    enum Shape {
+        #[serde(rename = "circle")]
+        Circle { center: Point, radius: u32 },
+        #[serde(rename = "dot")]
+        Dot(Point),
+        #[serde(rename = "none")]
+        Empty,
+    }
+
+    #[test]
+    fn reads_structs_across_lines() {
+        let value: (Point, Point) = from_str("1 2\n-3\n4").unwrap();
+        assert_eq!(value, (Point { x: 1, y: 2 }, Point { x: -3, y: 4 }));
+    }
+
+    #[test]
+    fn reads_sequences_until_end_of_line() {
+        let value: (u8, Vec<u8>, Vec<u8>, Vec<u8>) = from_str("3 1 2 3\n\n4 5").unwrap();
+        assert_eq!(value, (3, vec![1, 2, 3], vec![], vec![4, 5]));
+    }
+
+    #[test]
+    fn reads_length_prefixed_sequences() {
+        let value: Polygon = from_str("triangle 3\n0 0\n1 0\n0 1").unwrap();
+
+        assert_eq!(value.name, "triangle");
+        assert_eq!(value.points[2], Point { x: 0, y: 1 });
+    }
+
+    #[test]
+    fn rejects_length_prefixed_non_sequences() {
+        #[derive(Deserialize, Debug)]
+        #[allow(dead_code)]
+        struct Test {
+            #[serde(deserialize_with = "length_prefixed::deserialize")]
+            pair: (u8, u8),
+            values: Vec<u8>,
+        }
+
+        let result = from_str::<Test>("1 2\n3 4");
+        assert!(matches!(result, Err(DeserializeError::Unsupported { .. })));
+    }
+
+    #[test]
+    fn reads_enums_by_tag() {
+        let value: Vec<Shape> = from_str("circle 1 2 5 dot 3 4 none").unwrap();
+
+        assert_eq!(
+            value,
+            vec![
+                Shape::Circle {
+                    center: Point { x: 1, y: 2 },
+                    radius: 5
+                },
+                Shape::Dot(Point { x: 3, y: 4 }),
+                Shape::Empty,
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_options_and_maps() {
+        let value: (u8, Option<u8>, Option<u8>) = from_str("1 2").unwrap();
+        assert_eq!(value, (1, Some(2), None));
+
+        let value: BTreeMap<String, u8> = from_str("a 1 b 2").unwrap();
+        assert_eq!(
+            value,
+            BTreeMap::from([("a".to_owned(), 1), ("b".to_owned(), 2)])
+        );
+    }
+
+    #[test]
+    fn reads_from_token_reader() {
+        let mut input = TokenReader::new("1 2\n3\n4\nrest".as_bytes());
+
+        assert_eq!(input.deserialize::<Point>().unwrap(), Point { x: 1, y: 2 });
+        assert_eq!(input.deserialize::<Point>().unwrap(), Point { x: 3, y: 4 });
+        assert_eq!(input.line_raw().unwrap(), "rest");
+    }
+
+    #[test]
+    fn returns_error_on_trailing_tokens() {
+        let result = from_str::<Point>("1 2\n\n3");
+        assert!(matches!(result, Err(DeserializeError::TrailingTokens { token }) if token == "3"));
+
+        let mut input = TokenReader::new("1 2 3".as_bytes());
+        let result = input.deserialize::<Point>();
+        assert!(matches!(result, Err(DeserializeError::TrailingTokens { token }) if token == "3"));
+    }
+
+    #[test]
+    fn returns_error_on_invalid_token() {
+        let result = from_str::<Point>("1 x");
+        assert!(matches!(
+            result,
+            Err(DeserializeError::ParseError { token, .. }) if token == "x"
+        ));
+
+        let result = from_str::<Point>("1");
+        assert!(matches!(
+            result,
+            Err(DeserializeError::ReadError {
+                source: ReadLineError::EndOfFile
+            })
+        ));
+    }
+}
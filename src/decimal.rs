@@ -0,0 +1,209 @@
+use core::{fmt, str::FromStr};
+
+use crate::ParseDecimalError;
+
+/// An exact decimal number with at most `SCALE` digits after the decimal point, such as `3.1622`.
+///
+/// The number is stored as an [`i64`] scaled by `10^SCALE`, so `SCALE` may be at most 18.
+/// Tokens with more decimal places than `SCALE` are rejected, even if the extra digits are zeros,
+/// which makes the type usable for validating inputs.
+/// [`Display`](fmt::Display) writes exactly `SCALE` decimal places.
+///
+/// # Example
+///
+/// ```
+/// # use token_read::{Decimal, ToTokens};
+/// # use anyhow::Result;
+/// #
+/// # fn main() -> Result<()> {
+/// let prices: Vec<Decimal<2>> = "3.5 -0.25 12".parse_tokens()?;
+///
+/// assert_eq!(prices[0].to_scaled(), 350);
+/// assert_eq!(prices[1].to_string(), "-0.25");
+/// assert_eq!(prices[2], Decimal::from_scaled(1200));
+/// assert!("0.125".parse::<Decimal<2>>().is_err());
+/// #
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Decimal<const SCALE: u32>(i64);
+
+impl<const SCALE: u32> Decimal<SCALE> {
+    /// The number the value is scaled by.
+    const FACTOR: i64 = {
+        assert!(SCALE <= 18, "the scale of a decimal may be at most 18");
+        10i64.pow(SCALE)
+    };
+
+    /// Creates a decimal from its value multiplied by `10^SCALE`.
+    pub fn from_scaled(scaled: i64) -> Self {
+        // Checks the scale when the type is used.
+        let _ = Self::FACTOR;
+        Decimal(scaled)
+    }
+
+    /// Returns the value multiplied by `10^SCALE`.
+    pub fn to_scaled(self) -> i64 {
+        self.0
+    }
+
+    /// Returns the nearest floating-point number.
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 / Self::FACTOR as f64
+    }
+}
+
+impl<const SCALE: u32> FromStr for Decimal<SCALE> {
+    type Err = ParseDecimalError;
+
+    fn from_str(token: &str) -> Result<Self, Self::Err> {
+        let (negative, unsigned) = match token.as_bytes().first() {
+            Some(b'-') => (true, &token[1..]),
+            Some(b'+') => (false, &token[1..]),
+            _ => (false, token),
+        };
+
+        let (integer, fraction) = match unsigned.split_once('.') {
+            Some((integer, fraction)) if !fraction.is_empty() => (integer, fraction),
+            Some(_) => return Err(ParseDecimalError::InvalidDigit),
+            None => (unsigned, ""),
+        };
+
+        if integer.is_empty() {
+            return Err(match fraction.is_empty() {
+                true => ParseDecimalError::Empty,
+                false => ParseDecimalError::InvalidDigit,
+            });
+        }
+
+        if fraction.len() > SCALE as usize {
+            return Err(match fraction.bytes().all(|b| b.is_ascii_digit()) {
+                true => ParseDecimalError::TooPrecise { scale: SCALE },
+                false => ParseDecimalError::InvalidDigit,
+            });
+        }
+
+        // The missing decimal places are filled with zeros.
+        let padding = (fraction.len()..SCALE as usize).map(|_| b'0');
+        let digits = integer.bytes().chain(fraction.bytes()).chain(padding);
+
+        let mut magnitude: i128 = 0;
+
+        for digit in digits {
+            if !digit.is_ascii_digit() {
+                return Err(ParseDecimalError::InvalidDigit);
+            }
+
+            magnitude = magnitude * 10 + i128::from(digit - b'0');
+
+            // Stop before the magnitude could overflow, the result is out of range anyway.
+            if magnitude > i128::from(i64::MAX) + 1 {
+                return Err(ParseDecimalError::Overflow);
+            }
+        }
+
+        let value = if negative { -magnitude } else { magnitude };
+        let value = i64::try_from(value).map_err(|_| ParseDecimalError::Overflow)?;
+
+        Ok(Decimal::from_scaled(value))
+    }
+}
+
+impl<const SCALE: u32> fmt::Display for Decimal<SCALE> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let factor = Self::FACTOR.unsigned_abs();
+        let magnitude = self.0.unsigned_abs();
+
+        if self.0 < 0 {
+            f.write_str("-")?;
+        }
+
+        write!(f, "{}", magnitude / factor)?;
+
+        if SCALE > 0 {
+            write!(f, ".{:0width$}", magnitude % factor, width = SCALE as usize)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use proptest::prelude::*;
+
+    use crate::{Decimal, ParseDecimalError};
+
+    #[test]
+    fn parses_decimals() {
+        assert_eq!("3.1622".parse(), Ok(Decimal::<4>::from_scaled(31622)));
+        assert_eq!("3.1".parse(), Ok(Decimal::<4>::from_scaled(31000)));
+        assert_eq!("-0.5".parse(), Ok(Decimal::<1>::from_scaled(-5)));
+        assert_eq!("+7".parse(), Ok(Decimal::<0>::from_scaled(7)));
+        assert_eq!("-0.00".parse(), Ok(Decimal::<2>::from_scaled(0)));
+    }
+
+    #[test]
+    fn rejects_invalid_tokens() {
+        let parse = |token: &str| token.parse::<Decimal<2>>();
+
+        assert_eq!(parse(""), Err(ParseDecimalError::Empty));
+        assert_eq!(parse("-"), Err(ParseDecimalError::Empty));
+        assert_eq!(parse(".5"), Err(ParseDecimalError::InvalidDigit));
+        assert_eq!(parse("5."), Err(ParseDecimalError::InvalidDigit));
+        assert_eq!(parse("1.2.3"), Err(ParseDecimalError::InvalidDigit));
+        assert_eq!(parse("1e5"), Err(ParseDecimalError::InvalidDigit));
+        assert_eq!(parse("--1"), Err(ParseDecimalError::InvalidDigit));
+        assert_eq!(parse("1.x00"), Err(ParseDecimalError::InvalidDigit));
+    }
+
+    #[test]
+    fn rejects_too_many_decimal_places() {
+        assert_eq!(
+            "0.100".parse::<Decimal<2>>(),
+            Err(ParseDecimalError::TooPrecise { scale: 2 })
+        );
+        assert_eq!(
+            "1.5".parse::<Decimal<0>>(),
+            Err(ParseDecimalError::TooPrecise { scale: 0 })
+        );
+    }
+
+    #[test]
+    fn detects_overflow() {
+        assert_eq!(
+            "-9223372036854775.808".parse(),
+            Ok(Decimal::<3>::from_scaled(i64::MIN))
+        );
+        assert_eq!(
+            "9223372036854775.808".parse::<Decimal<3>>(),
+            Err(ParseDecimalError::Overflow)
+        );
+        assert_eq!(
+            "100000000000000000000000000000".parse::<Decimal<0>>(),
+            Err(ParseDecimalError::Overflow)
+        );
+    }
+
+    #[test]
+    fn displays_all_decimal_places() {
+        assert_eq!(Decimal::<3>::from_scaled(1500).to_string(), "1.500");
+        assert_eq!(Decimal::<2>::from_scaled(-5).to_string(), "-0.05");
+        assert_eq!(Decimal::<0>::from_scaled(42).to_string(), "42");
+        assert_eq!(
+            Decimal::<3>::from_scaled(i64::MIN).to_string(),
+            "-9223372036854775.808"
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn round_trips_through_display(value: i64) {
+            let decimal = Decimal::<4>::from_scaled(value);
+            prop_assert_eq!(decimal.to_string().parse(), Ok(decimal));
+        }
+    }
+}
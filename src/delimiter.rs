@@ -0,0 +1,391 @@
+use alloc::{string::String, sync::Arc, vec::Vec};
+use core::{fmt, mem, str};
+
+use crate::{FromTokens, Remainder, ToTokens, Tokens};
+
+#[cfg(doc)]
+use crate::Quoted;
+#[cfg(doc)]
+use crate::ReaderOptions;
+
+/// Describes how a line is split into tokens.
+///
+/// By default, tokens are separated by any amount of whitespace.
+/// [`Delimiter::quoted_whitespace`] works the same way, but keeps quoted strings together.
+/// With any other delimiter, every occurrence of the delimiter separates two tokens,
+/// so empty tokens are kept, and whitespace around each token is trimmed. A blank line contains no tokens.
+///
+/// It can be used with [`Split`] to tokenize strings
+/// or with [`ReaderOptions::delimiter`] to configure a reader.
+///
+/// # Example
+///
+/// ```
+/// # use token_read::{Delimiter, Split, ToTokens};
+/// # use anyhow::Result;
+/// #
+/// # fn main() -> Result<()> {
+/// let delimiter = Delimiter::chars(",;");
+/// let values: Vec<u32> = Split::new("1, 2;3", &delimiter).parse_tokens()?;
+///
+/// assert_eq!(values, vec![1, 2, 3]);
+/// #
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Delimiter {
+    inner: DelimiterInner,
+}
+
+#[derive(Clone)]
+enum DelimiterInner {
+    Whitespace,
+    QuotedWhitespace,
+    Chars(Vec<char>),
+    String(String),
+    Predicate(Arc<dyn Fn(char) -> bool + Send + Sync>),
+}
+
+impl Delimiter {
+    /// Splits at any amount of whitespace, the same way as [`str::split_whitespace`].
+    pub fn whitespace() -> Self {
+        Delimiter {
+            inner: DelimiterInner::Whitespace,
+        }
+    }
+
+    /// Splits at any amount of whitespace, except inside tokens starting with a double quote.
+    ///
+    /// Such a token continues until the closing quote, which can be escaped using a backslash,
+    /// and then until the next whitespace. The quotes are kept, so the token can be parsed using [`Quoted`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use token_read::{Delimiter, Quoted, Split, ToTokens};
+    /// # use anyhow::Result;
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let delimiter = Delimiter::quoted_whitespace();
+    /// let line = r#""New York" 8468000 "\"Big\" Apple""#;
+    /// let (Quoted(city), population, Quoted(nickname)): (Quoted<String>, u64, Quoted<String>) =
+    ///     Split::new(line, &delimiter).parse_tokens()?;
+    ///
+    /// assert_eq!(city, "New York");
+    /// assert_eq!(population, 8468000);
+    /// assert_eq!(nickname, "\"Big\" Apple");
+    /// #
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn quoted_whitespace() -> Self {
+        Delimiter {
+            inner: DelimiterInner::QuotedWhitespace,
+        }
+    }
+
+    /// Splits at every occurrence of any of the characters of the string.
+    pub fn chars(chars: &str) -> Self {
+        Delimiter {
+            inner: DelimiterInner::Chars(chars.chars().collect()),
+        }
+    }
+
+    /// Splits at every occurrence of the whole string.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the string is empty.
+    pub fn string(string: &str) -> Self {
+        assert!(!string.is_empty(), "delimiter must not be empty");
+
+        Delimiter {
+            inner: DelimiterInner::String(string.into()),
+        }
+    }
+
+    /// Splits at every character for which the function returns `true`.
+    pub fn predicate<F>(predicate: F) -> Self
+    where
+        F: Fn(char) -> bool + Send + Sync + 'static,
+    {
+        Delimiter {
+            inner: DelimiterInner::Predicate(Arc::new(predicate)),
+        }
+    }
+
+    /// Returns whether this is the default whitespace delimiter.
+    pub fn is_whitespace(&self) -> bool {
+        matches!(self.inner, DelimiterInner::Whitespace)
+    }
+
+    /// Creates an iterator over the tokens of a string.
+    pub fn tokens<'a>(&'a self, string: &'a str) -> SplitTokens<'a> {
+        let inner = match &self.inner {
+            DelimiterInner::Whitespace => SplitTokensInner::Whitespace(Tokens::new(string)),
+            DelimiterInner::QuotedWhitespace => SplitTokensInner::QuotedWhitespace(string),
+            _ if string.trim().is_empty() => SplitTokensInner::Delimited {
+                rest: None,
+                delimiter: self,
+            },
+            _ => SplitTokensInner::Delimited {
+                rest: Some(string),
+                delimiter: self,
+            },
+        };
+
+        SplitTokens { inner }
+    }
+
+    /// Finds the first delimiter in a string, returning its start and end.
+    fn find(&self, string: &str) -> Option<(usize, usize)> {
+        match &self.inner {
+            DelimiterInner::Whitespace | DelimiterInner::QuotedWhitespace => {
+                unreachable!("whitespace is handled separately")
+            }
+            DelimiterInner::Chars(chars) => find_char(string, |c| chars.contains(&c)),
+            DelimiterInner::String(delimiter) => string
+                .find(delimiter.as_str())
+                .map(|start| (start, start + delimiter.len())),
+            DelimiterInner::Predicate(predicate) => find_char(string, |c| predicate(c)),
+        }
+    }
+}
+
+impl Default for Delimiter {
+    fn default() -> Self {
+        Delimiter::whitespace()
+    }
+}
+
+impl fmt::Debug for Delimiter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.inner {
+            DelimiterInner::Whitespace => f.write_str("Whitespace"),
+            DelimiterInner::QuotedWhitespace => f.write_str("QuotedWhitespace"),
+            DelimiterInner::Chars(chars) => f.debug_tuple("Chars").field(chars).finish(),
+            DelimiterInner::String(string) => f.debug_tuple("String").field(string).finish(),
+            DelimiterInner::Predicate(_) => f.write_str("Predicate(..)"),
+        }
+    }
+}
+
+/// Finds the first character matching a predicate, returning its start and end.
+fn find_char(string: &str, predicate: impl Fn(char) -> bool) -> Option<(usize, usize)> {
+    string
+        .char_indices()
+        .find(|&(_, c)| predicate(c))
+        .map(|(start, c)| (start, start + c.len_utf8()))
+}
+
+/// Returns the length of the quoted string at the start of a string, including the quotes.
+///
+/// If the string doesn't start with a quote, zero is returned.
+/// If the closing quote is missing, [`None`] is returned.
+pub(crate) fn quoted_length(string: &str) -> Option<usize> {
+    let Some(quoted) = string.strip_prefix('"') else {
+        return Some(0);
+    };
+
+    let mut escaped = false;
+
+    for (index, c) in quoted.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => return Some(index + 2),
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// A string together with a [`Delimiter`], which can be parsed using [`ToTokens`].
+///
+/// # Example
+///
+/// ```
+/// # use token_read::{Delimiter, Split, ToTokens};
+/// # use anyhow::Result;
+/// #
+/// # fn main() -> Result<()> {
+/// let delimiter = Delimiter::string("::");
+/// let (module, item): (String, String) = Split::new("io::Read", &delimiter).parse_tokens()?;
+///
+/// assert_eq!(module, "io");
+/// assert_eq!(item, "Read");
+/// #
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Split<'a> {
+    string: &'a str,
+    delimiter: &'a Delimiter,
+}
+
+impl<'a> Split<'a> {
+    /// Wraps a string to be split using a delimiter.
+    pub fn new(string: &'a str, delimiter: &'a Delimiter) -> Self {
+        Split { string, delimiter }
+    }
+}
+
+impl<'a> ToTokens<'a> for Split<'a> {
+    type Iter = SplitTokens<'a>;
+
+    fn to_tokens(&'a self) -> Self::Iter {
+        self.delimiter.tokens(self.string)
+    }
+
+    fn parse_tokens<T>(&'a self) -> Result<T, T::Error>
+    where
+        T: FromTokens,
+    {
+        T::from_line_tokens(self.to_tokens())
+    }
+}
+
+/// An iterator over the tokens of a string, separated by a [`Delimiter`].
+#[derive(Debug, Clone)]
+pub struct SplitTokens<'a> {
+    inner: SplitTokensInner<'a>,
+}
+
+#[derive(Debug, Clone)]
+enum SplitTokensInner<'a> {
+    Whitespace(Tokens<'a>),
+    QuotedWhitespace(&'a str),
+    Delimited {
+        rest: Option<&'a str>,
+        delimiter: &'a Delimiter,
+    },
+}
+
+impl<'a> Iterator for SplitTokens<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.inner {
+            SplitTokensInner::Whitespace(tokens) => tokens.next(),
+            SplitTokensInner::QuotedWhitespace(rest) => {
+                let string = rest.trim_start();
+                let quoted = quoted_length(string).unwrap_or(string.len());
+
+                let end = string[quoted..]
+                    .find(char::is_whitespace)
+                    .map_or(string.len(), |end| quoted + end);
+                let (token, after) = string.split_at(end);
+
+                *rest = after;
+                (!token.is_empty()).then_some(token)
+            }
+            SplitTokensInner::Delimited { rest, delimiter } => {
+                let string = rest.take()?;
+
+                match delimiter.find(string) {
+                    Some((start, end)) => {
+                        *rest = Some(&string[end..]);
+                        Some(string[..start].trim())
+                    }
+                    None => Some(string.trim()),
+                }
+            }
+        }
+    }
+}
+
+impl<'a> Remainder<'a> for SplitTokens<'a> {
+    fn remainder(&mut self) -> &'a str {
+        match &mut self.inner {
+            SplitTokensInner::Whitespace(tokens) => tokens.remainder(),
+            SplitTokensInner::QuotedWhitespace(rest) => mem::take(rest).trim(),
+            SplitTokensInner::Delimited { rest, .. } => rest.take().map_or("", str::trim),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use crate::{Delimiter, Remainder, Split, ToTokens};
+
+    fn split<'a>(string: &'a str, delimiter: &'a Delimiter) -> Vec<&'a str> {
+        delimiter.tokens(string).collect()
+    }
+
+    #[test]
+    fn splits_at_whitespace_by_default() {
+        let delimiter = Delimiter::default();
+        assert_eq!(split(" a  b\t", &delimiter), ["a", "b"]);
+    }
+
+    #[test]
+    fn keeps_quoted_strings_together() {
+        let delimiter = Delimiter::quoted_whitespace();
+
+        assert_eq!(
+            split(r#" a "b c"  "d\" e"f g "#, &delimiter),
+            ["a", r#""b c""#, r#""d\" e"f"#, "g"]
+        );
+        assert_eq!(split(r#""a b"#, &delimiter), [r#""a b"#]);
+        assert_eq!(split(" ", &delimiter), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn returns_remainder() {
+        let delimiter = Delimiter::chars(",");
+        let mut tokens = delimiter.tokens("a, b ,c ");
+
+        assert_eq!(tokens.next(), Some("a"));
+        assert_eq!(tokens.remainder(), "b ,c");
+        assert_eq!(tokens.next(), None);
+    }
+
+    #[test]
+    fn splits_at_chars() {
+        let delimiter = Delimiter::chars(",;");
+        assert_eq!(split("a, b;c", &delimiter), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn keeps_empty_tokens() {
+        let delimiter = Delimiter::chars(",");
+        assert_eq!(split("a,,b,", &delimiter), ["a", "", "b", ""]);
+    }
+
+    #[test]
+    fn returns_no_tokens_for_blank_string() {
+        let delimiter = Delimiter::chars(",");
+        assert_eq!(split(" ", &delimiter), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn splits_at_string() {
+        let delimiter = Delimiter::string("->");
+        assert_eq!(split("a -> b-c", &delimiter), ["a", "b-c"]);
+    }
+
+    #[test]
+    fn splits_using_predicate() {
+        let delimiter = Delimiter::predicate(|c| !c.is_alphanumeric() && c != ' ');
+        assert_eq!(split("a:b|č d", &delimiter), ["a", "b", "č d"]);
+    }
+
+    #[test]
+    fn parses_split_string() {
+        let delimiter = Delimiter::chars(":");
+        let (a, b): (u8, char) = Split::new("1:x", &delimiter).parse_tokens().unwrap();
+
+        assert_eq!((a, b), (1, 'x'));
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_empty_string_delimiter() {
+        Delimiter::string("");
+    }
+}
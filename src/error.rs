@@ -1,27 +1,176 @@
+use alloc::{boxed::Box, string::String};
+use core::error::Error as StdError;
+#[cfg(feature = "std")]
 use std::io;
 use thiserror::Error;
 
 #[cfg(doc)]
-use crate::reader::TokenReader;
+use crate::{
+    BitString, Bytes, Canonical, Chars, Decimal, FromBytes, FromRecord, Index1, KeyValue, Quoted,
+    Record, TokenSource,
+};
+use core::num::ParseIntError;
 
-/// An error returned from [`TokenReader::line`].
+/// An error returned from [`TokenSource::line`] and [`TokenSource::byte_line`].
+///
+/// The `IoError` variant is only available with the `std` feature.
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum ReadTokensError<E> {
+    #[cfg(feature = "std")]
     #[error("input error")]
     IoError { source: io::Error },
     #[error("unexpected end of file")]
     EndOfFile,
+    #[error("invalid UTF-8 on line {line} at byte {offset}")]
+    InvalidUtf8 { line: usize, offset: usize },
     #[error("failed to parse line of tokens: \"{line}\"")]
     ParseError { source: E, line: String },
 }
 
-/// An error returned from [`TokenReader::line_raw`].
+/// An error returned from [`TokenSource::line_raw`] and other methods reading a single line.
+///
+/// The `IoError` variant is only available with the `std` feature.
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum ReadLineError {
+    #[cfg(feature = "std")]
     #[error("input error")]
     IoError { source: io::Error },
     #[error("unexpected end of file")]
     EndOfFile,
+    #[error("invalid UTF-8 on line {line} at byte {offset}")]
+    InvalidUtf8 { line: usize, offset: usize },
+}
+
+/// An error returned when parsing an integer from bytes using [`FromBytes`].
+///
+/// The variants correspond to the kinds of [`ParseIntError`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ParseIntBytesError {
+    #[error("cannot parse integer from empty string")]
+    Empty,
+    #[error("invalid digit found in string")]
+    InvalidDigit,
+    #[error("number too large to fit in target type")]
+    PosOverflow,
+    #[error("number too small to fit in target type")]
+    NegOverflow,
+}
+
+/// An error returned when parsing a [`Quoted`] token.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ParseQuotedError<E> {
+    #[error("missing closing quote")]
+    Unterminated,
+    #[error("unexpected characters after closing quote")]
+    TrailingCharacters,
+    #[error("invalid escape sequence \"\\{escape}\"")]
+    InvalidEscape { escape: char },
+    #[error("failed to parse quoted string")]
+    ParseError { source: E },
+}
+
+/// An error returned when parsing an [`Index1`] token.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ParseIndex1Error {
+    #[error("one-based index can't be zero")]
+    Zero,
+    #[error("failed to parse index")]
+    ParseError { source: ParseIntError },
+}
+
+/// An error returned when a [`BitString`] or the alphabet of [`Chars`] or [`Bytes`] contains an invalid character.
+///
+/// Positions are counted from zero, in characters for strings and in bytes for byte tokens.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum AlphabetError {
+    #[error("invalid character {character:?} at position {position}")]
+    InvalidCharacter { character: char, position: usize },
+    #[error("invalid byte {byte:#04x} at position {position}")]
+    InvalidByte { byte: u8, position: usize },
+}
+
+/// An error returned when parsing a [`Canonical`] token.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ParseCanonicalError<E> {
+    #[error("token isn't in canonical form")]
+    NotCanonical,
+    #[error("failed to parse value")]
+    ParseError { source: E },
+}
+
+/// An error returned when parsing a [`Decimal`] token.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ParseDecimalError {
+    #[error("cannot parse decimal from empty string")]
+    Empty,
+    #[error("invalid digit found in string")]
+    InvalidDigit,
+    #[error("decimal out of range")]
+    Overflow,
+    #[error("decimal has more than {scale} decimal places")]
+    TooPrecise { scale: u32 },
+}
+
+/// An error returned when parsing a [`KeyValue`] token.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ParseKeyValueError<E> {
+    #[error("missing \"=\" between key and value")]
+    MissingSeparator,
+    #[error("failed to parse value")]
+    ParseError { source: E },
+}
+
+/// An error returned when parsing a [`Record`] from tokens.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ParseRecordError {
+    #[error("missing \"=\" between key and value in token \"{token}\"")]
+    MissingSeparator { token: String },
+    #[error("duplicate key \"{key}\"")]
+    DuplicateKey { key: String },
+}
+
+/// An error returned when reading values from a [`Record`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum RecordError<E> {
+    #[error("missing key \"{key}\"")]
+    MissingKey { key: String },
+    #[error("unknown key \"{key}\"")]
+    UnknownKey { key: String },
+    #[error("failed to parse value of key \"{key}\"")]
+    ParseError { key: String, source: E },
+}
+
+impl<E> RecordError<E> {
+    /// Converts the error returned when parsing the value, keeping the key.
+    pub fn map_source<F>(self, map: impl FnOnce(E) -> F) -> RecordError<F> {
+        match self {
+            RecordError::MissingKey { key } => RecordError::MissingKey { key },
+            RecordError::UnknownKey { key } => RecordError::UnknownKey { key },
+            RecordError::ParseError { key, source } => RecordError::ParseError {
+                key,
+                source: map(source),
+            },
+        }
+    }
+}
+
+/// An error returned when parsing a field of a type implementing [`FromRecord`].
+///
+/// It wraps the error of the field type, as the fields can have different types.
+#[derive(Error, Debug)]
+#[error(transparent)]
+pub struct ParseFieldError(Box<dyn StdError + Send + Sync>);
+
+impl ParseFieldError {
+    /// Wraps the error returned when parsing a field.
+    pub fn new<E>(error: E) -> Self
+    where
+        E: StdError + Send + Sync + 'static,
+    {
+        ParseFieldError(Box::new(error))
+    }
 }
 
 /// An error returned when parsing a constant amount of tokens.
@@ -43,8 +192,12 @@ pub enum ParseTokenPatternError<E> {
 impl<E> From<ReadLineError> for ReadTokensError<E> {
     fn from(value: ReadLineError) -> Self {
         match value {
+            #[cfg(feature = "std")]
             ReadLineError::IoError { source } => ReadTokensError::IoError { source },
             ReadLineError::EndOfFile => ReadTokensError::EndOfFile,
+            ReadLineError::InvalidUtf8 { line, offset } => {
+                ReadTokensError::InvalidUtf8 { line, offset }
+            }
         }
     }
 }
@@ -0,0 +1,392 @@
+use core::{
+    any::TypeId,
+    marker::PhantomData,
+    mem::{self, ManuallyDrop},
+    str::FromStr,
+};
+
+use crate::FromBytes;
+
+#[cfg(doc)]
+use crate::{FromTokens, ReaderOptions};
+
+/// A trait for types with a specialized parser that is faster than their [`FromStr`] implementation.
+///
+/// Implementations are provided for all primitive integers and floats.
+/// They accept exactly the same tokens as [`FromStr`] and produce identical results and errors.
+/// Tokens the specialized parser can't handle, such as `inf` or floats with many significant digits,
+/// are passed on to [`FromStr`].
+///
+/// When the `fast-parse` feature is enabled, the implementations of [`FromTokens`] use this trait
+/// for primitive numbers automatically.
+pub trait FastParse: FromStr {
+    /// Parses a value from a token, like [`str::parse`].
+    fn fast_parse(token: &str) -> Result<Self, Self::Err>;
+}
+
+/// A trait for types that can be parsed from a single token by the implementations of [`FromTokens`].
+///
+/// It's implemented for all types implementing [`FromStr`].
+/// When the `fast-parse` feature is enabled, primitive numbers are parsed using [`FastParse`].
+pub trait ParseToken: FromStr {
+    /// Parses a value from a token, like [`str::parse`].
+    fn parse_token(token: &str) -> Result<Self, Self::Err>;
+
+    /// Parses a value from a token, reading primitive integers in the given radix.
+    ///
+    /// It's used for lines read with [`ReaderOptions::integer_radix`].
+    /// Other types are parsed like in [`ParseToken::parse_token`].
+    fn parse_token_in_radix(token: &str, radix: u32) -> Result<Self, Self::Err>;
+}
+
+impl<T> ParseToken for T
+where
+    T: FromStr,
+{
+    fn parse_token(token: &str) -> Result<Self, Self::Err> {
+        #[cfg(feature = "fast-parse")]
+        {
+            macro_rules! dispatch {
+                ($($t:ty)*) => {
+                    $(
+                        if let Some(result) = as_primitive::<T, $t>(|| <$t>::fast_parse(token)) {
+                            return result;
+                        }
+                    )*
+                };
+            }
+
+            dispatch!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize f32 f64);
+        }
+
+        token.parse()
+    }
+
+    fn parse_token_in_radix(token: &str, radix: u32) -> Result<Self, Self::Err> {
+        if radix != 10 {
+            macro_rules! dispatch {
+                ($($t:ty)*) => {
+                    $(
+                        if let Some(result) = as_primitive::<T, $t>(|| <$t>::from_str_radix(token, radix)) {
+                            return result;
+                        }
+                    )*
+                };
+            }
+
+            dispatch!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize);
+        }
+
+        T::parse_token(token)
+    }
+}
+
+/// Returns the [`TypeId`] of a type, which doesn't have to be `'static`.
+///
+/// Types differing only in lifetimes have the same ID.
+fn type_id<T: ?Sized>() -> TypeId {
+    trait NonStaticAny {
+        fn type_id(&self) -> TypeId
+        where
+            Self: 'static;
+    }
+
+    impl<T: ?Sized> NonStaticAny for PhantomData<T> {
+        fn type_id(&self) -> TypeId
+        where
+            Self: 'static,
+        {
+            TypeId::of::<T>()
+        }
+    }
+
+    let phantom = PhantomData::<T>;
+    let any: &dyn NonStaticAny = &phantom;
+
+    // SAFETY: The method only returns the type ID and never touches any data of the lifetime,
+    // so extending the lifetime of the trait object can't be observed.
+    let any: &(dyn NonStaticAny + 'static) = unsafe { mem::transmute(any) };
+    any.type_id()
+}
+
+/// Calls a parser for the primitive type `P` if `T` is `P`, returning its result as the types of `T`.
+///
+/// Returns [`None`] without calling the parser if the types differ.
+pub(crate) fn as_primitive<T, P>(
+    parse: impl FnOnce() -> Result<P, P::Err>,
+) -> Option<Result<T, T::Err>>
+where
+    T: FromStr,
+    P: FromStr + 'static,
+    P::Err: 'static,
+{
+    if type_id::<T>() != TypeId::of::<P>() {
+        return None;
+    }
+
+    let result = ManuallyDrop::new(parse());
+
+    // SAFETY: Primitive types have no lifetimes, so a type with the same ID has to be `P` itself.
+    // The result is therefore the same type and it's moved out of the `ManuallyDrop` exactly once.
+    Some(unsafe { mem::transmute_copy::<Result<P, P::Err>, Result<T, T::Err>>(&result) })
+}
+
+macro_rules! impl_int {
+    ($($t:ty)*) => {
+        $(
+            impl FastParse for $t {
+                fn fast_parse(token: &str) -> Result<Self, Self::Err> {
+                    <$t>::from_bytes(token.as_bytes()).or_else(|_| token.parse())
+                }
+            }
+        )*
+    };
+}
+
+impl_int!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize);
+
+/// Splits a decimal number into its sign, significant digits and a power of ten.
+///
+/// Returns [`None`] for invalid tokens and for tokens with more than 19 significant digits.
+fn parse_decimal(token: &[u8]) -> Option<(bool, u64, i32)> {
+    let (negative, token) = match token {
+        [b'-', rest @ ..] => (true, rest),
+        [b'+', rest @ ..] => (false, rest),
+        _ => (false, token),
+    };
+
+    let mut mantissa: u64 = 0;
+    let mut significant_digits = 0;
+    let mut any_digits = false;
+    let mut exponent: i32 = 0;
+    let mut rest = token;
+
+    let mut push_digit = |digit: u8| {
+        any_digits = true;
+
+        if mantissa != 0 || digit != 0 {
+            if significant_digits == 19 {
+                return false;
+            }
+
+            significant_digits += 1;
+            mantissa = mantissa * 10 + u64::from(digit);
+        }
+
+        true
+    };
+
+    while let [digit @ b'0'..=b'9', tail @ ..] = rest {
+        if !push_digit(digit - b'0') {
+            return None;
+        }
+
+        rest = tail;
+    }
+
+    if let [b'.', tail @ ..] = rest {
+        rest = tail;
+
+        while let [digit @ b'0'..=b'9', tail @ ..] = rest {
+            if !push_digit(digit - b'0') {
+                return None;
+            }
+
+            exponent -= 1;
+            rest = tail;
+        }
+    }
+
+    if !any_digits {
+        return None;
+    }
+
+    if let [b'e' | b'E', tail @ ..] = rest {
+        let (exponent_negative, tail) = match tail {
+            [b'-', tail @ ..] => (true, tail),
+            [b'+', tail @ ..] => (false, tail),
+            _ => (false, tail),
+        };
+
+        if tail.is_empty() || tail.len() > 4 || !tail.iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+
+        let value = tail
+            .iter()
+            .fold(0, |value, digit| value * 10 + i32::from(digit - b'0'));
+
+        exponent += if exponent_negative { -value } else { value };
+        rest = &[];
+    }
+
+    rest.is_empty().then_some((negative, mantissa, exponent))
+}
+
+macro_rules! impl_float {
+    ($t:ty, $mantissa_bits:literal, [$($power:literal),*]) => {
+        impl FastParse for $t {
+            /// Parses a float, using an exact algorithm for numbers with a small mantissa and exponent.
+            ///
+            /// If both the mantissa and the power of ten are exactly representable,
+            /// a single multiplication or division produces a correctly rounded result.
+            fn fast_parse(token: &str) -> Result<Self, Self::Err> {
+                const POWERS: &[$t] = &[$($power),*];
+
+                let fast = parse_decimal(token.as_bytes()).and_then(|(negative, mantissa, exponent)| {
+                    let power = *POWERS.get(exponent.unsigned_abs() as usize)?;
+
+                    if mantissa > 1 << $mantissa_bits {
+                        return None;
+                    }
+
+                    let value = mantissa as $t;
+                    let value = if exponent < 0 { value / power } else { value * power };
+
+                    Some(if negative { -value } else { value })
+                });
+
+                fast.map_or_else(|| token.parse(), Ok)
+            }
+        }
+    };
+}
+
+impl_float!(
+    f32,
+    24,
+    [1e0, 1e1, 1e2, 1e3, 1e4, 1e5, 1e6, 1e7, 1e8, 1e9, 1e10]
+);
+impl_float!(
+    f64,
+    53,
+    [
+        1e0, 1e1, 1e2, 1e3, 1e4, 1e5, 1e6, 1e7, 1e8, 1e9, 1e10, 1e11, 1e12, 1e13, 1e14, 1e15, 1e16,
+        1e17, 1e18, 1e19, 1e20, 1e21, 1e22
+    ]
+);
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::{FastParse, ParseToken, ToTokens};
+
+    fn assert_same<T>(token: &str)
+    where
+        T: FastParse + std::fmt::Debug + PartialEq,
+        T::Err: std::fmt::Debug + PartialEq,
+    {
+        assert_eq!(
+            T::fast_parse(token),
+            token.parse::<T>(),
+            "parsing {token:?}"
+        );
+    }
+
+    fn assert_same_float<T>(token: &str)
+    where
+        T: FastParse + std::fmt::Debug + Into<f64>,
+        T::Err: std::fmt::Debug + PartialEq,
+    {
+        let real = T::fast_parse(token).map(|v| v.into().to_bits());
+        let expected = token.parse::<T>().map(|v| v.into().to_bits());
+        assert_eq!(real, expected, "parsing {token:?}");
+    }
+
+    #[test]
+    fn parses_special_floats() {
+        let inputs = [
+            "",
+            ".",
+            "-",
+            "+",
+            "e5",
+            "1e",
+            "1e+",
+            "1.5e-3",
+            ".5",
+            "5.",
+            "-0",
+            "-0.0",
+            "inf",
+            "-infinity",
+            "NaN",
+            "1e400",
+            "1e-400",
+            "0e999",
+            "1_0",
+            "1.2.3",
+            "1E5",
+            "+.5e+2",
+            "12345678901234567890",
+            "9007199254740993",
+            "0.000000000000000000001",
+        ];
+
+        for input in inputs {
+            assert_same_float::<f64>(input);
+            assert_same_float::<f32>(input);
+        }
+    }
+
+    #[test]
+    fn parses_tokens_with_correct_type() {
+        assert_eq!(u8::parse_token("200"), Ok(200));
+        assert_eq!(i16::parse_token("-200"), Ok(-200));
+        assert_eq!(f32::parse_token("0.5"), Ok(0.5));
+        assert_eq!(String::parse_token("abc"), Ok("abc".to_owned()));
+        assert!(u8::parse_token("300").is_err());
+    }
+
+    #[test]
+    fn parses_non_static_types() {
+        struct Borrowed<'a>(core::marker::PhantomData<&'a ()>);
+
+        impl core::str::FromStr for Borrowed<'_> {
+            type Err = ();
+
+            fn from_str(_: &str) -> Result<Self, Self::Err> {
+                Ok(Borrowed(core::marker::PhantomData))
+            }
+        }
+
+        fn parse<'a>(line: &str) -> (Borrowed<'a>, u32) {
+            line.parse_tokens().unwrap()
+        }
+
+        assert_eq!(parse("x 5").1, 5);
+    }
+
+    proptest! {
+        #[test]
+        fn parses_arbitrary_integer_strings(token in "[+-]?[0-9]{0,40}x?") {
+            assert_same::<u8>(&token);
+            assert_same::<i8>(&token);
+            assert_same::<u32>(&token);
+            assert_same::<i64>(&token);
+            assert_same::<u128>(&token);
+            assert_same::<isize>(&token);
+        }
+
+        #[test]
+        fn parses_integers(value: i64) {
+            let token = value.to_string();
+            assert_same::<i64>(&token);
+            assert_same::<i32>(&token);
+            assert_same::<u64>(&token);
+        }
+
+        #[test]
+        fn parses_arbitrary_float_strings(token in "[+-]?[0-9]{0,25}(\\.[0-9]{0,25})?([eE][+-]?[0-9]{0,5})?") {
+            assert_same_float::<f64>(&token);
+            assert_same_float::<f32>(&token);
+        }
+
+        #[test]
+        fn parses_floats(value: f64) {
+            assert_same_float::<f64>(&value.to_string());
+            assert_same_float::<f64>(&format!("{value:e}"));
+        }
+    }
+}
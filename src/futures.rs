@@ -0,0 +1,82 @@
+//! Asynchronous reading from types implementing [`AsyncBufRead`](futures_io::AsyncBufRead) from [futures](https://crates.io/crates/futures).
+//!
+//! This module is only available with the `futures` feature.
+//!
+//! # Example
+//!
+//! ```
+//! # use anyhow::Result;
+//! use futures::{executor::block_on, io::Cursor, StreamExt};
+//! use token_read::futures::AsyncTokenReader;
+//!
+//! # fn main() -> Result<()> {
+//! # block_on(async {
+//! let mut input = AsyncTokenReader::new(Cursor::new("2\n1 a\n2 b\n"));
+//!
+//! let (count,): (usize,) = input.line().await?;
+//! let lines: Vec<(u8, char)> = input
+//!     .take::<(u8, char)>(count)
+//!     .map(Result::unwrap)
+//!     .collect()
+//!     .await;
+//!
+//! assert_eq!(lines, vec![(1, 'a'), (2, 'b')]);
+//! #
+//! #   Ok(())
+//! # })
+//! # }
+//! ```
+
+async_reader!(futures_io::AsyncBufRead);
+
+#[cfg(test)]
+mod tests {
+    use futures::{executor::block_on, io::BufReader, StreamExt};
+
+    use super::AsyncTokenReader;
+    use crate::{ReadLineError, ReadTokensError};
+
+    #[test]
+    fn reads_lines() {
+        block_on(async {
+            let input: &[u8] = b"First\r\n1 2 3\nlast";
+            let mut input = AsyncTokenReader::new(BufReader::with_capacity(4, input));
+
+            assert_eq!(input.line_raw().await.unwrap(), "First");
+            assert_eq!(input.line::<Vec<u8>>().await.unwrap(), vec![1, 2, 3]);
+            assert_eq!(input.line_raw().await.unwrap(), "last");
+            assert!(matches!(
+                input.line_raw().await,
+                Err(ReadLineError::EndOfFile)
+            ));
+        });
+    }
+
+    #[test]
+    fn returns_invalid_utf8() {
+        block_on(async {
+            let mut input = AsyncTokenReader::new(b"ok\nab\xffc".as_slice());
+            assert_eq!(input.line_raw().await.unwrap(), "ok");
+
+            assert!(matches!(
+                input.line::<Vec<String>>().await,
+                Err(ReadTokensError::InvalidUtf8 { line: 2, offset: 2 })
+            ));
+        });
+    }
+
+    #[test]
+    fn take_streams_lines() {
+        block_on(async {
+            let mut input = AsyncTokenReader::new(b"1 a\n2 b\n3 c".as_slice());
+            let lines: Vec<(u8, char)> = input
+                .take::<(u8, char)>(2)
+                .map(Result::unwrap)
+                .collect()
+                .await;
+
+            assert_eq!(lines, vec![(1, 'a'), (2, 'b')]);
+            assert_eq!(input.line_raw().await.unwrap(), "3 c");
+        });
+    }
+}
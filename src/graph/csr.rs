@@ -0,0 +1,141 @@
+use std::ops::Range;
+
+#[cfg(doc)]
+use super::EdgeList;
+
+/// A graph in the compressed sparse row representation.
+///
+/// The neighbours of all vertices are stored in a single contiguous array,
+/// which makes iterating over them very cache friendly.
+/// It can be created using [`EdgeList::csr`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Csr<W> {
+    offsets: Vec<usize>,
+    targets: Vec<usize>,
+    weights: Vec<W>,
+}
+
+impl<W> Csr<W> {
+    /// Builds the representation from edges given in the direction they can be traversed in.
+    ///
+    /// The neighbours of each vertex keep the order in which they were given.
+    pub(super) fn from_arcs<'a, I>(vertex_count: usize, arcs: I) -> Self
+    where
+        I: Iterator<Item = (usize, usize, &'a W)> + Clone,
+        W: Clone + 'a,
+    {
+        // Edge lists with a vertex count of `usize::MAX` are rejected while reading.
+        let mut offsets = vec![0; vertex_count.checked_add(1).expect("too many vertices")];
+
+        for (from, _, _) in arcs.clone() {
+            offsets[from + 1] += 1;
+        }
+
+        for vertex in 0..vertex_count {
+            offsets[vertex + 1] += offsets[vertex];
+        }
+
+        let arc_count = offsets[vertex_count];
+        let mut next = offsets.clone();
+        let mut slots = Vec::with_capacity(arc_count);
+        let mut targets = Vec::with_capacity(arc_count);
+        let mut weights = Vec::with_capacity(arc_count);
+
+        for (from, to, weight) in arcs {
+            slots.push(next[from]);
+            targets.push(to);
+            weights.push(weight.clone());
+            next[from] += 1;
+        }
+
+        // Moves every arc into its slot, placing at least one arc in its final position per swap.
+        for index in 0..arc_count {
+            while slots[index] != index {
+                let slot = slots[index];
+
+                slots.swap(index, slot);
+                targets.swap(index, slot);
+                weights.swap(index, slot);
+            }
+        }
+
+        Csr {
+            offsets,
+            targets,
+            weights,
+        }
+    }
+
+    /// Returns the number of vertices of the graph.
+    pub fn vertex_count(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    /// Returns the neighbours of a vertex.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vertex is out of range.
+    pub fn neighbours(&self, vertex: usize) -> &[usize] {
+        &self.targets[self.range(vertex)]
+    }
+
+    /// Returns the weights of the edges leading from a vertex,
+    /// in the same order as [`Csr::neighbours`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vertex is out of range.
+    pub fn weights(&self, vertex: usize) -> &[W] {
+        &self.weights[self.range(vertex)]
+    }
+
+    /// Iterates over the neighbours of a vertex together with the weight of the connecting edge.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vertex is out of range.
+    pub fn edges(&self, vertex: usize) -> impl Iterator<Item = (usize, &W)> {
+        self.neighbours(vertex)
+            .iter()
+            .copied()
+            .zip(self.weights(vertex))
+    }
+
+    fn range(&self, vertex: usize) -> Range<usize> {
+        self.offsets[vertex]..self.offsets[vertex + 1]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{graph::EdgeListOptions, TokenReader, TokenSource};
+
+    #[test]
+    fn converts_to_csr() {
+        let mut input = TokenReader::new("4 3\n1 2 7\n3 1 8\n1 4 9".as_bytes());
+        let graph = input
+            .graph::<(usize, usize, u8)>(EdgeListOptions::new())
+            .unwrap();
+        let csr = graph.csr();
+
+        assert_eq!(csr.vertex_count(), 4);
+        assert_eq!(csr.neighbours(0), [1, 2, 3]);
+        assert_eq!(csr.weights(0), [7, 8, 9]);
+        assert_eq!(csr.neighbours(1), [0]);
+        assert_eq!(csr.neighbours(2), [0]);
+        assert_eq!(csr.edges(3).collect::<Vec<_>>(), vec![(0, &9)]);
+    }
+
+    #[test]
+    fn converts_empty_graph_to_csr() {
+        let mut input = TokenReader::new("2 0".as_bytes());
+        let graph = input
+            .graph::<(usize, usize)>(EdgeListOptions::new())
+            .unwrap();
+        let csr = graph.csr();
+
+        assert_eq!(csr.vertex_count(), 2);
+        assert!(csr.neighbours(1).is_empty());
+    }
+}
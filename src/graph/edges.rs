@@ -0,0 +1,486 @@
+use std::{collections::HashSet, str::FromStr};
+
+use crate::{FromTokens, TokenSource};
+
+use super::{Csr, ReadGraphError};
+
+/// A trait for types that can be read as a single line of an edge list.
+///
+/// It is implemented for `(usize, usize)`, representing an unweighted edge,
+/// and for `(usize, usize, W)`, representing an edge with a weight of type `W`.
+pub trait EdgeTokens: FromTokens {
+    /// The type of the edge weight, [`()`](unit) for unweighted edges.
+    type Weight;
+
+    /// Splits the parsed line into the two endpoints and the weight.
+    fn into_parts(self) -> (usize, usize, Self::Weight);
+}
+
+impl EdgeTokens for (usize, usize) {
+    type Weight = ();
+
+    fn into_parts(self) -> (usize, usize, Self::Weight) {
+        (self.0, self.1, ())
+    }
+}
+
+impl<W> EdgeTokens for (usize, usize, W)
+where
+    W: FromStr,
+{
+    type Weight = W;
+
+    fn into_parts(self) -> (usize, usize, Self::Weight) {
+        self
+    }
+}
+
+/// Options controlling how an edge list is interpreted and validated.
+///
+/// By default, the graph is undirected, vertices are numbered from one
+/// and both self-loops and multiple edges are allowed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EdgeListOptions {
+    directed: bool,
+    one_indexed: bool,
+    self_loops: bool,
+    multi_edges: bool,
+}
+
+impl EdgeListOptions {
+    /// Creates the default options.
+    pub fn new() -> Self {
+        EdgeListOptions {
+            directed: false,
+            one_indexed: true,
+            self_loops: true,
+            multi_edges: true,
+        }
+    }
+
+    /// Sets whether edges only lead from the first vertex to the second.
+    pub fn directed(mut self, directed: bool) -> Self {
+        self.directed = directed;
+        self
+    }
+
+    /// Sets whether vertices in the input are numbered from one instead of zero.
+    ///
+    /// One-indexed vertices are converted to zero-based indices while reading.
+    pub fn one_indexed(mut self, one_indexed: bool) -> Self {
+        self.one_indexed = one_indexed;
+        self
+    }
+
+    /// Sets whether edges from a vertex to itself are accepted.
+    pub fn allow_self_loops(mut self, allow: bool) -> Self {
+        self.self_loops = allow;
+        self
+    }
+
+    /// Sets whether several edges between the same pair of vertices are accepted.
+    ///
+    /// In undirected graphs, the edges `u v` and `v u` are considered to be the same.
+    pub fn allow_multi_edges(mut self, allow: bool) -> Self {
+        self.multi_edges = allow;
+        self
+    }
+}
+
+impl Default for EdgeListOptions {
+    fn default() -> Self {
+        EdgeListOptions::new()
+    }
+}
+
+/// A single edge of an [`EdgeList`], with zero-based endpoints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Edge<W> {
+    pub from: usize,
+    pub to: usize,
+    pub weight: W,
+}
+
+/// A graph read from an edge list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EdgeList<W> {
+    vertex_count: usize,
+    directed: bool,
+    edges: Vec<Edge<W>>,
+}
+
+impl<W> EdgeList<W> {
+    /// Returns the number of vertices of the graph.
+    pub fn vertex_count(&self) -> usize {
+        self.vertex_count
+    }
+
+    /// Returns whether the graph is directed.
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    /// Returns the edges in the order they were read.
+    pub fn edges(&self) -> &[Edge<W>] {
+        &self.edges
+    }
+
+    /// Consumes the graph, returning the edges in the order they were read.
+    pub fn into_edges(self) -> Vec<Edge<W>> {
+        self.edges
+    }
+
+    /// Returns the neighbours of each vertex, ignoring weights.
+    ///
+    /// For undirected graphs, every edge is present in the lists of both endpoints,
+    /// except for self-loops, which are only listed once.
+    pub fn neighbours(&self) -> Vec<Vec<usize>> {
+        self.lists(|to, _| to)
+    }
+
+    /// Returns the neighbours of each vertex together with the weight of the connecting edge.
+    ///
+    /// See [`EdgeList::neighbours`] for how undirected edges are handled.
+    pub fn adjacency_list(&self) -> Vec<Vec<(usize, W)>>
+    where
+        W: Clone,
+    {
+        self.lists(|to, weight| (to, weight.clone()))
+    }
+
+    /// Converts the graph into the compressed sparse row representation.
+    ///
+    /// See [`EdgeList::neighbours`] for how undirected edges are handled.
+    pub fn csr(&self) -> Csr<W>
+    where
+        W: Clone,
+    {
+        Csr::from_arcs(self.vertex_count, self.arcs())
+    }
+
+    /// Builds a list for every vertex containing the mapped arcs leading from it.
+    fn lists<T, F>(&self, mut map: F) -> Vec<Vec<T>>
+    where
+        F: FnMut(usize, &W) -> T,
+    {
+        // The vertex count comes from the input, so the lists are only created once they are needed.
+        let mut lists = Vec::with_capacity(self.vertex_count.min(1 << 16));
+
+        for (from, to, weight) in self.arcs() {
+            if lists.len() <= from {
+                lists.resize_with(from + 1, Vec::new);
+            }
+
+            lists[from].push(map(to, weight));
+        }
+
+        lists.resize_with(self.vertex_count, Vec::new);
+        lists
+    }
+
+    /// Iterates over all edges in the direction they can be traversed in.
+    fn arcs(&self) -> impl Iterator<Item = (usize, usize, &W)> + Clone {
+        let directed = self.directed;
+
+        self.edges.iter().flat_map(move |edge| {
+            let forward = Some((edge.from, edge.to, &edge.weight));
+            let backward =
+                (!directed && edge.from != edge.to).then_some((edge.to, edge.from, &edge.weight));
+
+            forward.into_iter().chain(backward)
+        })
+    }
+}
+
+/// Reads a graph from any [`TokenSource`], see [`TokenSource::graph`].
+pub(crate) fn read_graph<S, E>(
+    input: &mut S,
+    options: EdgeListOptions,
+) -> Result<EdgeList<E::Weight>, ReadGraphError<E::Error>>
+where
+    S: TokenSource,
+    E: EdgeTokens,
+{
+    let (vertex_count, edge_count): (usize, usize) = input
+        .line()
+        .map_err(|source| ReadGraphError::HeaderError { source })?;
+
+    read_edges::<_, E>(input, vertex_count, edge_count, options)
+}
+
+/// Reads the edges of a graph from any [`TokenSource`], see [`TokenSource::edges`].
+pub(crate) fn read_edges<S, E>(
+    input: &mut S,
+    vertex_count: usize,
+    edge_count: usize,
+    options: EdgeListOptions,
+) -> Result<EdgeList<E::Weight>, ReadGraphError<E::Error>>
+where
+    S: TokenSource,
+    E: EdgeTokens,
+{
+    // The CSR representation stores one offset past the last vertex.
+    if vertex_count.checked_add(1).is_none() {
+        return Err(ReadGraphError::TooManyVertices {
+            count: vertex_count,
+        });
+    }
+
+    // The edge count comes from the input, so it can't be trusted with a large allocation.
+    let mut edges = Vec::with_capacity(edge_count.min(1 << 16));
+    let mut seen = HashSet::new();
+
+    for (index, edge) in input.take::<E>(edge_count).enumerate() {
+        let edge = edge.map_err(|source| ReadGraphError::EdgeError {
+            source,
+            edge: index,
+        })?;
+        let (from, to, weight) = edge.into_parts();
+
+        let convert = |vertex: usize| {
+            let converted = if options.one_indexed {
+                vertex.checked_sub(1)
+            } else {
+                Some(vertex)
+            };
+
+            converted
+                .filter(|&v| v < vertex_count)
+                .ok_or(ReadGraphError::VertexOutOfRange {
+                    edge: index,
+                    vertex,
+                })
+        };
+
+        let edge = Edge {
+            from: convert(from)?,
+            to: convert(to)?,
+            weight,
+        };
+
+        if !options.self_loops && edge.from == edge.to {
+            return Err(ReadGraphError::SelfLoop {
+                edge: index,
+                vertex: from,
+            });
+        }
+
+        if !options.multi_edges {
+            let key = if options.directed {
+                (edge.from, edge.to)
+            } else {
+                (edge.from.min(edge.to), edge.from.max(edge.to))
+            };
+
+            if !seen.insert(key) {
+                return Err(ReadGraphError::MultiEdge {
+                    edge: index,
+                    from,
+                    to,
+                });
+            }
+        }
+
+        edges.push(edge);
+    }
+
+    Ok(EdgeList {
+        vertex_count,
+        directed: options.directed,
+        edges,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        graph::{Edge, EdgeListOptions, ReadGraphError},
+        ReadTokensError, SliceReader, TokenReader, TokenSource,
+    };
+
+    #[test]
+    fn reads_undirected_graph() {
+        let mut input = TokenReader::new("4 3\n1 2\n2 3\n4 1".as_bytes());
+        let graph = input
+            .graph::<(usize, usize)>(EdgeListOptions::new())
+            .unwrap();
+
+        assert_eq!(graph.vertex_count(), 4);
+        assert_eq!(
+            graph.neighbours(),
+            vec![vec![1, 3], vec![0, 2], vec![1], vec![0]]
+        );
+    }
+
+    #[test]
+    fn reads_graph_from_slice() {
+        let mut input = SliceReader::new("3 2\n1 2\n3 1\nnext");
+        let graph = input
+            .graph::<(usize, usize)>(EdgeListOptions::new())
+            .unwrap();
+
+        assert_eq!(graph.neighbours(), vec![vec![1, 2], vec![0], vec![0]]);
+        assert_eq!(input.line_raw().unwrap(), "next");
+    }
+
+    #[test]
+    fn reads_directed_weighted_graph() {
+        let mut input = TokenReader::new("3 2\n0 1 5\n2 1 -3".as_bytes());
+        let options = EdgeListOptions::new().directed(true).one_indexed(false);
+        let graph = input.graph::<(usize, usize, i32)>(options).unwrap();
+
+        assert_eq!(
+            graph.edges(),
+            [
+                Edge {
+                    from: 0,
+                    to: 1,
+                    weight: 5
+                },
+                Edge {
+                    from: 2,
+                    to: 1,
+                    weight: -3
+                }
+            ]
+        );
+        assert_eq!(
+            graph.adjacency_list(),
+            vec![vec![(1, 5)], vec![], vec![(1, -3)]]
+        );
+    }
+
+    #[test]
+    fn lists_undirected_self_loop_once() {
+        let mut input = TokenReader::new("2 1\n2 2".as_bytes());
+        let graph = input
+            .graph::<(usize, usize)>(EdgeListOptions::new())
+            .unwrap();
+
+        assert_eq!(graph.neighbours(), vec![vec![], vec![1]]);
+    }
+
+    #[test]
+    fn reads_edges_without_header() {
+        let mut input = TokenReader::new("1 2\n2 3\nnext".as_bytes());
+        let graph = input
+            .edges::<(usize, usize)>(3, 2, EdgeListOptions::new())
+            .unwrap();
+
+        assert_eq!(graph.edges().len(), 2);
+        assert_eq!(input.line_raw().unwrap(), "next");
+    }
+
+    #[test]
+    fn returns_error_on_vertex_out_of_range() {
+        let mut input = TokenReader::new("3 2\n1 2\n2 4".as_bytes());
+        let result = input.graph::<(usize, usize)>(EdgeListOptions::new());
+
+        assert!(matches!(
+            result,
+            Err(ReadGraphError::VertexOutOfRange { edge: 1, vertex: 4 })
+        ));
+    }
+
+    #[test]
+    fn returns_error_on_zero_vertex_when_one_indexed() {
+        let mut input = TokenReader::new("3 1\n0 2".as_bytes());
+        let result = input.graph::<(usize, usize)>(EdgeListOptions::new());
+
+        assert!(matches!(
+            result,
+            Err(ReadGraphError::VertexOutOfRange { edge: 0, vertex: 0 })
+        ));
+    }
+
+    #[test]
+    fn returns_error_on_self_loop() {
+        let mut input = TokenReader::new("3 2\n1 2\n3 3".as_bytes());
+        let options = EdgeListOptions::new().allow_self_loops(false);
+        let result = input.graph::<(usize, usize)>(options);
+
+        assert!(matches!(
+            result,
+            Err(ReadGraphError::SelfLoop { edge: 1, vertex: 3 })
+        ));
+    }
+
+    #[test]
+    fn returns_error_on_undirected_multi_edge() {
+        let mut input = TokenReader::new("3 2\n1 2\n2 1".as_bytes());
+        let options = EdgeListOptions::new().allow_multi_edges(false);
+        let result = input.graph::<(usize, usize)>(options);
+
+        assert!(matches!(
+            result,
+            Err(ReadGraphError::MultiEdge {
+                edge: 1,
+                from: 2,
+                to: 1
+            })
+        ));
+    }
+
+    #[test]
+    fn allows_opposite_directed_edges() {
+        let mut input = TokenReader::new("3 2\n1 2\n2 1".as_bytes());
+        let options = EdgeListOptions::new()
+            .directed(true)
+            .allow_multi_edges(false);
+
+        assert!(input.graph::<(usize, usize)>(options).is_ok());
+    }
+
+    #[test]
+    fn returns_error_on_missing_edge() {
+        let mut input = TokenReader::new("3 2\n1 2".as_bytes());
+        let result = input.graph::<(usize, usize)>(EdgeListOptions::new());
+
+        assert!(matches!(
+            result,
+            Err(ReadGraphError::EdgeError {
+                source: ReadTokensError::EndOfFile,
+                edge: 1
+            })
+        ));
+    }
+
+    #[test]
+    fn returns_error_on_missing_edges_with_huge_count() {
+        let mut input = TokenReader::new("3 10000000000000\n1 2".as_bytes());
+        let result = input.graph::<(usize, usize)>(EdgeListOptions::new());
+
+        assert!(matches!(
+            result,
+            Err(ReadGraphError::EdgeError {
+                source: ReadTokensError::EndOfFile,
+                edge: 1
+            })
+        ));
+    }
+
+    #[test]
+    fn returns_error_on_too_many_vertices() {
+        let header = format!("{} 0", usize::MAX);
+        let mut input = TokenReader::new(header.as_bytes());
+        let result = input.graph::<(usize, usize)>(EdgeListOptions::new());
+
+        assert!(matches!(
+            result,
+            Err(ReadGraphError::TooManyVertices { count: usize::MAX })
+        ));
+    }
+
+    #[test]
+    fn returns_error_on_invalid_header() {
+        let mut input = TokenReader::new("3\n1 2".as_bytes());
+        let result = input.graph::<(usize, usize)>(EdgeListOptions::new());
+
+        assert!(matches!(
+            result,
+            Err(ReadGraphError::HeaderError {
+                source: ReadTokensError::ParseError { .. }
+            })
+        ));
+    }
+}
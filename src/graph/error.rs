@@ -0,0 +1,75 @@
+use std::num::ParseIntError;
+
+use thiserror::Error;
+
+use crate::{impls::ParseTuple2Error, ParseTokenPatternError, ReadTokensError};
+
+#[cfg(doc)]
+use super::Tree;
+#[cfg(doc)]
+use crate::TokenSource;
+
+/// An error returned when the `n m` line preceding an edge list can't be read.
+pub type ReadHeaderError =
+    ReadTokensError<ParseTokenPatternError<ParseTuple2Error<ParseIntError, ParseIntError>>>;
+
+/// An error returned from [`TokenSource::graph`] and [`TokenSource::edges`].
+///
+/// Edges are identified by their index in the edge list, starting from zero.
+/// Vertices are reported as they appeared in the input, i.e. before converting them to zero-based indices.
+#[derive(Error, Debug)]
+pub enum ReadGraphError<E> {
+    #[error("failed to read graph header")]
+    HeaderError { source: ReadHeaderError },
+    #[error("failed to read edge {edge}")]
+    EdgeError {
+        source: ReadTokensError<E>,
+        edge: usize,
+    },
+    #[error("vertex count {count} is too large")]
+    TooManyVertices { count: usize },
+    #[error("vertex {vertex} of edge {edge} is out of range")]
+    VertexOutOfRange { edge: usize, vertex: usize },
+    #[error("edge {edge} is a self-loop on vertex {vertex}")]
+    SelfLoop { edge: usize, vertex: usize },
+    #[error("edge {edge} duplicates an earlier edge between {from} and {to}")]
+    MultiEdge { edge: usize, from: usize, to: usize },
+}
+
+/// An error returned from [`Tree::from_parents`].
+///
+/// Vertices are reported as zero-based indices.
+#[derive(Error, Debug)]
+pub enum TreeError {
+    #[error("no vertex is the root")]
+    NoRoot,
+    #[error("vertex {vertex} is another root")]
+    MultipleRoots { vertex: usize },
+    #[error("parent {parent} of vertex {vertex} is out of range")]
+    ParentOutOfRange { vertex: usize, parent: usize },
+    #[error("vertex {vertex} doesn't lead to the root")]
+    NotATree { vertex: usize },
+}
+
+/// An error returned from [`TokenSource::parent_tree`] and [`TokenSource::tree`].
+///
+/// Vertices are reported as they appeared in the input and edges by their index, starting from zero.
+#[derive(Error, Debug)]
+pub enum ReadTreeError<E> {
+    #[error("failed to read parents")]
+    ParentsError {
+        source: ReadTokensError<ParseIntError>,
+    },
+    #[error("failed to read edges")]
+    GraphError { source: ReadGraphError<E> },
+    #[error("the tree has no vertices")]
+    Empty,
+    #[error("got {real} parents, expected {expected}")]
+    WrongParentCount { real: usize, expected: usize },
+    #[error("parent {parent} of vertex {vertex} is out of range")]
+    ParentOutOfRange { vertex: usize, parent: usize },
+    #[error("vertex {vertex} doesn't lead to the root")]
+    NotATree { vertex: usize },
+    #[error("edge {edge} creates a cycle")]
+    Cycle { edge: usize },
+}
@@ -0,0 +1,53 @@
+//! Helpers for reading graphs given as edge lists.
+//!
+//! Many problems describe a graph as a line with the vertex count `n` and the edge count `m`,
+//! followed by `m` lines each containing the two endpoints of an edge and optionally its weight:
+//!
+//! ```txt
+//! 4 3
+//! 1 2
+//! 2 3
+//! 4 1
+//! ```
+//!
+//! Such input can be read using [`TokenSource::graph`] and then converted into an adjacency
+//! list or a [`Csr`] representation.
+//! Trees can be read either from an edge list using [`TokenSource::tree`]
+//! or from a line of parents using [`TokenSource::parent_tree`].
+//!
+//! # Example
+//!
+//! ```
+//! # use token_read::{TokenReader, TokenSource};
+//! # use token_read::graph::EdgeListOptions;
+//! # use anyhow::Result;
+//! #
+//! # fn main() -> Result<()> {
+//! let mut input = TokenReader::new("3 2\n1 2 10\n2 3 20".as_bytes());
+//! let graph = input.graph::<(usize, usize, u64)>(EdgeListOptions::new())?;
+//!
+//! assert_eq!(graph.adjacency_list(), vec![
+//!     vec![(1, 10)],
+//!     vec![(0, 10), (2, 20)],
+//!     vec![(1, 20)],
+//! ]);
+//! #
+//! #   Ok(())
+//! # }
+//! ```
+
+#[cfg(doc)]
+use crate::TokenSource;
+
+mod csr;
+mod edges;
+mod error;
+mod tree;
+
+pub use csr::Csr;
+pub use edges::{Edge, EdgeList, EdgeListOptions, EdgeTokens};
+pub use error::{ReadGraphError, ReadHeaderError, ReadTreeError, TreeError};
+pub use tree::Tree;
+
+pub(crate) use edges::{read_edges, read_graph};
+pub(crate) use tree::{read_parent_tree, read_tree};
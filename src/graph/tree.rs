@@ -0,0 +1,404 @@
+use std::num::ParseIntError;
+
+use crate::TokenSource;
+
+use super::{read_edges, EdgeListOptions, EdgeTokens, ReadTreeError, TreeError};
+
+/// A rooted tree with zero-based vertices.
+///
+/// It can be read using [`TokenSource::parent_tree`] or [`TokenSource::tree`],
+/// or created from an array of parents using [`Tree::from_parents`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tree<W = ()> {
+    root: usize,
+    parents: Vec<Option<(usize, W)>>,
+    children: Vec<Vec<usize>>,
+}
+
+impl Tree {
+    /// Creates a tree from the parent of every vertex, with the root having no parent.
+    ///
+    /// Returns an error if there isn't exactly one root or if some vertices don't lead to it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use token_read::graph::Tree;
+    /// # use anyhow::Result;
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let tree = Tree::from_parents(vec![Some(2), Some(2), None])?;
+    ///
+    /// assert_eq!(tree.root(), 2);
+    /// assert_eq!(tree.children(2), [0, 1]);
+    /// #
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn from_parents(parents: Vec<Option<usize>>) -> Result<Self, TreeError> {
+        let parents = parents
+            .into_iter()
+            .map(|parent| parent.map(|parent| (parent, ())))
+            .collect();
+
+        Tree::from_weighted_parents(parents)
+    }
+}
+
+impl<W> Tree<W> {
+    fn from_weighted_parents(parents: Vec<Option<(usize, W)>>) -> Result<Self, TreeError> {
+        #[derive(Clone, Copy, PartialEq, Eq)]
+        enum State {
+            Unvisited,
+            Visiting,
+            Reachable,
+        }
+
+        let count = parents.len();
+        let mut root = None;
+        let mut children = vec![Vec::new(); count];
+
+        for (vertex, parent) in parents.iter().enumerate() {
+            match parent {
+                Some((parent, _)) if *parent >= count => {
+                    return Err(TreeError::ParentOutOfRange {
+                        vertex,
+                        parent: *parent,
+                    })
+                }
+                Some((parent, _)) => children[*parent].push(vertex),
+                None if root.is_some() => return Err(TreeError::MultipleRoots { vertex }),
+                None => root = Some(vertex),
+            }
+        }
+
+        let root = root.ok_or(TreeError::NoRoot)?;
+
+        let mut states = vec![State::Unvisited; count];
+        let mut path = Vec::new();
+
+        for start in 0..count {
+            let mut vertex = start;
+
+            while states[vertex] == State::Unvisited {
+                states[vertex] = State::Visiting;
+                path.push(vertex);
+
+                match &parents[vertex] {
+                    Some((parent, _)) => vertex = *parent,
+                    None => break,
+                }
+            }
+
+            if states[vertex] == State::Visiting && parents[vertex].is_some() {
+                return Err(TreeError::NotATree { vertex: start });
+            }
+
+            for vertex in path.drain(..) {
+                states[vertex] = State::Reachable;
+            }
+        }
+
+        Ok(Tree {
+            root,
+            parents,
+            children,
+        })
+    }
+
+    /// Returns the number of vertices of the tree.
+    pub fn vertex_count(&self) -> usize {
+        self.parents.len()
+    }
+
+    /// Returns the root of the tree.
+    pub fn root(&self) -> usize {
+        self.root
+    }
+
+    /// Returns the parent of a vertex, or [`None`] for the root.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vertex is out of range.
+    pub fn parent(&self, vertex: usize) -> Option<usize> {
+        self.parents[vertex].as_ref().map(|(parent, _)| *parent)
+    }
+
+    /// Returns the weight of the edge between a vertex and its parent, or [`None`] for the root.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vertex is out of range.
+    pub fn parent_weight(&self, vertex: usize) -> Option<&W> {
+        self.parents[vertex].as_ref().map(|(_, weight)| weight)
+    }
+
+    /// Returns the children of a vertex.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vertex is out of range.
+    pub fn children(&self, vertex: usize) -> &[usize] {
+        &self.children[vertex]
+    }
+}
+
+/// Reads a tree from a line of parents from any [`TokenSource`], see [`TokenSource::parent_tree`].
+pub(crate) fn read_parent_tree<S: TokenSource>(
+    input: &mut S,
+    vertex_count: usize,
+) -> Result<Tree, ReadTreeError<ParseIntError>> {
+    if vertex_count == 0 {
+        return Err(ReadTreeError::Empty);
+    }
+
+    let parents: Vec<usize> = input
+        .line()
+        .map_err(|source| ReadTreeError::ParentsError { source })?;
+
+    let expected = vertex_count - 1;
+
+    if parents.len() != expected {
+        return Err(ReadTreeError::WrongParentCount {
+            real: parents.len(),
+            expected,
+        });
+    }
+
+    let mut converted = Vec::with_capacity(vertex_count);
+    converted.push(None);
+
+    for (index, parent) in parents.into_iter().enumerate() {
+        let vertex = index + 2;
+
+        match parent.checked_sub(1) {
+            Some(parent) if parent < vertex_count => converted.push(Some(parent)),
+            _ => return Err(ReadTreeError::ParentOutOfRange { vertex, parent }),
+        }
+    }
+
+    Tree::from_parents(converted).map_err(|error| match error {
+        TreeError::NotATree { vertex } => ReadTreeError::NotATree { vertex: vertex + 1 },
+        _ => unreachable!("vertex 1 should be the only root"),
+    })
+}
+
+/// Reads a tree from a list of edges from any [`TokenSource`], see [`TokenSource::tree`].
+pub(crate) fn read_tree<S, E>(
+    input: &mut S,
+    vertex_count: usize,
+    root: usize,
+    options: EdgeListOptions,
+) -> Result<Tree<E::Weight>, ReadTreeError<E::Error>>
+where
+    S: TokenSource,
+    E: EdgeTokens,
+{
+    if vertex_count == 0 {
+        return Err(ReadTreeError::Empty);
+    }
+
+    assert!(root < vertex_count, "root {root} is out of range");
+
+    let options = options.directed(false);
+    let edges = read_edges::<_, E>(input, vertex_count, vertex_count - 1, options)
+        .map_err(|source| ReadTreeError::GraphError { source })?;
+
+    let mut components: Vec<usize> = (0..vertex_count).collect();
+    let mut incident = vec![Vec::new(); vertex_count];
+    let mut edges: Vec<_> = edges.into_edges().into_iter().map(Some).collect();
+
+    for (index, edge) in edges.iter().flatten().enumerate() {
+        let from = find_component(&mut components, edge.from);
+        let to = find_component(&mut components, edge.to);
+
+        if from == to {
+            return Err(ReadTreeError::Cycle { edge: index });
+        }
+
+        components[from] = to;
+        incident[edge.from].push(index);
+        incident[edge.to].push(index);
+    }
+
+    let mut parents: Vec<_> = (0..vertex_count).map(|_| None).collect();
+    let mut stack = vec![root];
+
+    while let Some(vertex) = stack.pop() {
+        for &index in &incident[vertex] {
+            if let Some(edge) = edges[index].take() {
+                let child = if edge.from == vertex {
+                    edge.to
+                } else {
+                    edge.from
+                };
+
+                parents[child] = Some((vertex, edge.weight));
+                stack.push(child);
+            }
+        }
+    }
+
+    Ok(Tree::from_weighted_parents(parents).expect("edges should form a tree"))
+}
+
+/// Finds the representative of a vertex in a union-find structure, compressing the path.
+fn find_component(components: &mut [usize], mut vertex: usize) -> usize {
+    while components[vertex] != vertex {
+        components[vertex] = components[components[vertex]];
+        vertex = components[vertex];
+    }
+
+    vertex
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        graph::{EdgeListOptions, ReadGraphError, ReadTreeError, Tree, TreeError},
+        TokenReader, TokenSource,
+    };
+
+    #[test]
+    fn reads_parent_tree() {
+        let mut input = TokenReader::new("1 1 3 3".as_bytes());
+        let tree = input.parent_tree(5).unwrap();
+
+        assert_eq!(tree.vertex_count(), 5);
+        assert_eq!(tree.root(), 0);
+        assert_eq!(tree.parent(0), None);
+        assert_eq!(tree.parent(4), Some(2));
+        assert_eq!(tree.children(0), [1, 2]);
+        assert_eq!(tree.children(2), [3, 4]);
+    }
+
+    #[test]
+    fn reads_single_vertex_parent_tree() {
+        let mut input = TokenReader::new("\n".as_bytes());
+        let tree = input.parent_tree(1).unwrap();
+
+        assert_eq!(tree.vertex_count(), 1);
+    }
+
+    #[test]
+    fn returns_error_on_empty_parent_tree() {
+        let mut input = TokenReader::new("next".as_bytes());
+        let result = input.parent_tree(0);
+
+        assert!(matches!(result, Err(ReadTreeError::Empty)));
+        assert_eq!(input.line_raw().unwrap(), "next");
+    }
+
+    #[test]
+    fn returns_error_on_wrong_parent_count() {
+        let mut input = TokenReader::new("1 1".as_bytes());
+        let result = input.parent_tree(4);
+
+        assert!(matches!(
+            result,
+            Err(ReadTreeError::WrongParentCount {
+                real: 2,
+                expected: 3
+            })
+        ));
+    }
+
+    #[test]
+    fn returns_error_on_parent_out_of_range() {
+        let mut input = TokenReader::new("1 5".as_bytes());
+        let result = input.parent_tree(3);
+
+        assert!(matches!(
+            result,
+            Err(ReadTreeError::ParentOutOfRange {
+                vertex: 3,
+                parent: 5
+            })
+        ));
+    }
+
+    #[test]
+    fn returns_error_on_parent_cycle() {
+        let mut input = TokenReader::new("1 4 5 3".as_bytes());
+        let result = input.parent_tree(5);
+
+        assert!(matches!(result, Err(ReadTreeError::NotATree { vertex: 3 })));
+    }
+
+    #[test]
+    fn creates_tree_from_parents() {
+        let tree = Tree::from_parents(vec![Some(1), None, Some(1)]).unwrap();
+
+        assert_eq!(tree.root(), 1);
+        assert_eq!(tree.children(1), [0, 2]);
+    }
+
+    #[test]
+    fn returns_error_on_multiple_roots() {
+        let result = Tree::from_parents(vec![None, Some(0), None]);
+        assert!(matches!(
+            result,
+            Err(TreeError::MultipleRoots { vertex: 2 })
+        ));
+    }
+
+    #[test]
+    fn returns_error_on_no_root() {
+        let result = Tree::from_parents(vec![Some(1), Some(0)]);
+        assert!(matches!(result, Err(TreeError::NoRoot)));
+    }
+
+    #[test]
+    fn reads_tree_from_edges() {
+        let mut input = TokenReader::new("2 1\n3 2\n4 2".as_bytes());
+        let tree = input
+            .tree::<(usize, usize)>(4, 1, EdgeListOptions::new())
+            .unwrap();
+
+        assert_eq!(tree.root(), 1);
+        assert_eq!(tree.children(1), [0, 2, 3]);
+        assert_eq!(tree.parent(3), Some(1));
+    }
+
+    #[test]
+    fn keeps_edge_weights() {
+        let mut input = TokenReader::new("0 1 a\n2 1 b".as_bytes());
+        let options = EdgeListOptions::new().one_indexed(false);
+        let tree = input.tree::<(usize, usize, char)>(3, 0, options).unwrap();
+
+        assert_eq!(tree.parent_weight(0), None);
+        assert_eq!(tree.parent_weight(1), Some(&'a'));
+        assert_eq!(tree.parent_weight(2), Some(&'b'));
+    }
+
+    #[test]
+    fn returns_error_on_empty_edge_tree() {
+        let mut input = TokenReader::new("next".as_bytes());
+        let result = input.tree::<(usize, usize)>(0, 0, EdgeListOptions::new());
+
+        assert!(matches!(result, Err(ReadTreeError::Empty)));
+        assert_eq!(input.line_raw().unwrap(), "next");
+    }
+
+    #[test]
+    fn returns_error_on_edge_cycle() {
+        let mut input = TokenReader::new("1 2\n2 3\n3 1\n".as_bytes());
+        let result = input.tree::<(usize, usize)>(4, 0, EdgeListOptions::new());
+
+        assert!(matches!(result, Err(ReadTreeError::Cycle { edge: 2 })));
+    }
+
+    #[test]
+    fn returns_error_on_invalid_edge() {
+        let mut input = TokenReader::new("2 3".as_bytes());
+        let result = input.tree::<(usize, usize)>(2, 0, EdgeListOptions::new());
+
+        assert!(matches!(
+            result,
+            Err(ReadTreeError::GraphError {
+                source: ReadGraphError::VertexOutOfRange { edge: 0, vertex: 3 }
+            })
+        ));
+    }
+}
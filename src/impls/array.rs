@@ -1,8 +1,8 @@
-use std::str::FromStr;
+use core::str::FromStr;
 
 use arrayvec::ArrayVec;
 
-use crate::{FromTokens, ParseTokenPatternError};
+use crate::{FromByteTokens, FromBytes, FromTokens, ParseToken, ParseTokenPatternError, Remainder};
 
 impl<T, const N: usize> FromTokens for [T; N]
 where
@@ -14,26 +14,55 @@ where
     where
         I: Iterator<Item = &'a str>,
     {
-        let mut array: ArrayVec<T, N> = ArrayVec::new();
+        collect_array(tokens.map(T::parse_token))
+    }
+
+    fn from_line_tokens<'a, I>(tokens: I) -> Result<Self, Self::Error>
+    where
+        I: Remainder<'a>,
+    {
+        let radix = tokens.integer_radix();
+        collect_array(tokens.map(|token| T::parse_token_in_radix(token, radix)))
+    }
+}
+
+impl<T, const N: usize> FromByteTokens for [T; N]
+where
+    T: FromBytes,
+{
+    type Error = ParseTokenPatternError<T::Err>;
+
+    fn from_byte_tokens<'a, I>(tokens: I) -> Result<Self, Self::Error>
+    where
+        I: Iterator<Item = &'a [u8]>,
+    {
+        collect_array(tokens.map(T::from_bytes))
+    }
+}
 
-        for token in tokens.map(|v| v.parse()) {
-            let token = token.map_err(|source| ParseTokenPatternError::ParseError { source })?;
+fn collect_array<T, E, I, const N: usize>(tokens: I) -> Result<[T; N], ParseTokenPatternError<E>>
+where
+    I: Iterator<Item = Result<T, E>>,
+{
+    let mut array: ArrayVec<T, N> = ArrayVec::new();
 
-            array
-                .try_push(token)
-                .map_err(|_| ParseTokenPatternError::TooManyTokens { expected: N })?;
-        }
+    for token in tokens {
+        let token = token.map_err(|source| ParseTokenPatternError::ParseError { source })?;
 
         array
-            .into_inner()
-            .map_err(|e| ParseTokenPatternError::TooFewTokens {
-                real: e.len(),
-                expected: N,
-            })
+            .try_push(token)
+            .map_err(|_| ParseTokenPatternError::TooManyTokens { expected: N })?;
     }
+
+    array
+        .into_inner()
+        .map_err(|e| ParseTokenPatternError::TooFewTokens {
+            real: e.len(),
+            expected: N,
+        })
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use crate::{ParseTokenPatternError, ReadTokensError, TokenReader};
 
@@ -44,6 +73,13 @@ mod tests {
         assert_eq!(value, [10, 11, 12, 13]);
     }
 
+    #[test]
+    fn reads_array_from_bytes() {
+        let mut input = TokenReader::new("10 11 12 13".as_bytes());
+        let value: [u8; 4] = input.byte_line().unwrap();
+        assert_eq!(value, [10, 11, 12, 13]);
+    }
+
     #[test]
     fn returns_error_on_too_many_elements() {
         let mut input = TokenReader::new("10 11 12 13 14".as_bytes());
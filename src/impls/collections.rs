@@ -1,16 +1,20 @@
-use std::{
-    collections::{BTreeSet, BinaryHeap, HashSet, LinkedList, VecDeque},
-    hash::Hash,
-    str::FromStr,
+use alloc::{
+    collections::{BTreeSet, BinaryHeap, LinkedList, VecDeque},
+    vec::Vec,
 };
+#[cfg(feature = "std")]
+use core::hash::Hash;
+use core::str::FromStr;
+#[cfg(feature = "std")]
+use std::collections::HashSet;
 
-use crate::FromTokens;
+use crate::{FromByteTokens, FromBytes, FromTokens, ParseToken, Remainder};
 
 macro_rules! impl_collect {
-    ($ty:ident, $bound:tt $(+ $others:tt )*) => {
+    ($ty:ident $(, $others:tt )*) => {
         impl<T> FromTokens for $ty<T>
         where
-            T: $bound $(+ $others)*,
+            T: FromStr $(+ $others)*,
         {
             type Error = T::Err;
 
@@ -18,20 +22,43 @@ macro_rules! impl_collect {
             where
                 I: Iterator<Item = &'a str>,
             {
-                tokens.map(|v| v.parse()).collect()
+                tokens.map(T::parse_token).collect()
+            }
+
+            fn from_line_tokens<'a, I>(tokens: I) -> Result<Self, Self::Error>
+            where
+                I: Remainder<'a>,
+            {
+                let radix = tokens.integer_radix();
+                tokens.map(|token| T::parse_token_in_radix(token, radix)).collect()
+            }
+        }
+
+        impl<T> FromByteTokens for $ty<T>
+        where
+            T: FromBytes $(+ $others)*,
+        {
+            type Error = T::Err;
+
+            fn from_byte_tokens<'a, I>(tokens: I) -> Result<Self, Self::Error>
+            where
+                I: Iterator<Item = &'a [u8]>,
+            {
+                tokens.map(T::from_bytes).collect()
             }
         }
     };
 }
 
-impl_collect!(Vec, FromStr);
-impl_collect!(LinkedList, FromStr);
-impl_collect!(VecDeque, FromStr);
-impl_collect!(BTreeSet, FromStr + Ord);
-impl_collect!(HashSet, FromStr + Hash + Eq);
-impl_collect!(BinaryHeap, FromStr + Ord);
+impl_collect!(Vec);
+impl_collect!(LinkedList);
+impl_collect!(VecDeque);
+impl_collect!(BTreeSet, Ord);
+#[cfg(feature = "std")]
+impl_collect!(HashSet, Hash, Eq);
+impl_collect!(BinaryHeap, Ord);
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use std::collections::{BTreeSet, BinaryHeap, HashSet, LinkedList, VecDeque};
 
@@ -57,6 +84,16 @@ mod tests {
     impl_colletion_test!(reads_into_btree_set, BTreeSet);
     impl_colletion_test!(reads_into_hash_set, HashSet);
 
+    #[test]
+    fn reads_bytes_into_vec() {
+        let mut input = TokenReader::new("41 42 43".as_bytes());
+
+        let real: Vec<u8> = input.byte_line().unwrap();
+        let expected: Vec<u8> = (41..=43).collect();
+
+        assert_eq!(real, expected);
+    }
+
     #[test]
     fn reads_into_binary_heap() {
         let mut input = TokenReader::new("41 42 43".as_bytes());
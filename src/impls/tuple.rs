@@ -1,27 +1,65 @@
-use std::str::FromStr;
+use alloc::borrow::ToOwned;
+use core::convert::Infallible;
 
 use thiserror::Error;
 
-use crate::{FromTokens, ParseTokenPatternError};
+use crate::{
+    optional::only_trailing_optional, FromByteTokens, FromBytes, FromTokens,
+    ParseTokenPatternError, Remainder, RestOfLine, TupleElement,
+};
 
 macro_rules! impl_tuple {
     ($len:literal, $error_name:ident; $($index:literal, $success_type:ident, $field_name:ident, $error_type:ident, $error_variant:ident;)*) => {
-        impl<$($success_type: FromStr),*> FromTokens for ($($success_type,)*)
+        impl<$($success_type: TupleElement),*> FromTokens for ($($success_type,)*)
         {
             type Error = ParseTokenPatternError<$error_name<$($success_type::Err),*>>;
 
-            fn from_tokens<'a, I>(mut tokens: I) -> Result<Self, Self::Error>
+            fn from_tokens<'a, I>(tokens: I) -> Result<Self, Self::Error>
             where
                 I: Iterator<Item = &'a str>,
+            {
+                let mut tokens = tokens.fuse();
+                parse_prefix!(tokens, 10, $len, $error_name; $($index, $success_type, $field_name, $error_variant;)*);
+
+                match tokens.next() {
+                    Some(_) => Err(ParseTokenPatternError::TooManyTokens { expected: $len }),
+                    None => Ok(($($field_name,)*)),
+                }
+            }
+
+            fn from_line_tokens<'a, I>(tokens: I) -> Result<Self, Self::Error>
+            where
+                I: Remainder<'a>,
+            {
+                // The empty tuple doesn't parse any elements.
+                #[allow(unused_variables)]
+                let radix = tokens.integer_radix();
+                let mut tokens = tokens.fuse();
+                parse_prefix!(tokens, radix, $len, $error_name; $($index, $success_type, $field_name, $error_variant;)*);
+
+                match tokens.next() {
+                    Some(_) => Err(ParseTokenPatternError::TooManyTokens { expected: $len }),
+                    None => Ok(($($field_name,)*)),
+                }
+            }
+        }
+
+        impl<$($success_type: FromBytes),*> FromByteTokens for ($($success_type,)*)
+        {
+            type Error = ParseTokenPatternError<$error_name<$($success_type::Err),*>>;
+
+            fn from_byte_tokens<'a, I>(mut tokens: I) -> Result<Self, Self::Error>
+            where
+                I: Iterator<Item = &'a [u8]>,
             {
                 $(
-                    let $field_name = tokens
+                    let token = tokens
                         .next()
                         .ok_or_else(|| ParseTokenPatternError::TooFewTokens {
                             real: $index,
                             expected: $len,
-                        })?
-                        .parse()
+                        })?;
+                    let $field_name = $success_type::from_bytes(token)
                         .map_err(|source| ParseTokenPatternError::ParseError {
                             source: $error_name::$error_variant { source },
                         })?;
@@ -45,11 +83,72 @@ macro_rules! impl_tuple {
     };
 }
 
+/// Parses the elements of a tuple, or the ones before a [`RestOfLine`], reading integers in the given radix.
+macro_rules! parse_prefix {
+    ($tokens:ident, $radix:expr, $len:literal, $error_name:ident; $($index:literal, $success_type:ident, $field_name:ident, $error_variant:ident;)*) => {
+        const {
+            assert!(
+                only_trailing_optional(&[$($success_type::OPTIONAL),*]),
+                "only trailing tuple elements may be optional",
+            )
+        };
+
+        $(
+            let $field_name = match $tokens.next() {
+                Some(token) => $success_type::parse_element(token, $radix)
+                    .map_err(|source| ParseTokenPatternError::ParseError {
+                        source: $error_name::$error_variant { source },
+                    })?,
+                None => $success_type::missing_element().ok_or(
+                    ParseTokenPatternError::TooFewTokens {
+                        real: $index,
+                        expected: $len,
+                    },
+                )?,
+            };
+        )*
+    };
+}
+
+macro_rules! impl_tuple_rest {
+    ($len:literal, $error_name:ident; $($index:literal, $success_type:ident, $field_name:ident, $error_variant:ident;)* $rest_name:ident;) => {
+        impl<$($success_type: TupleElement),*> FromTokens for ($($success_type,)* RestOfLine,)
+        {
+            type Error = ParseTokenPatternError<$error_name<$($success_type::Err,)* Infallible>>;
+
+            fn from_tokens<'a, I>(tokens: I) -> Result<Self, Self::Error>
+            where
+                I: Iterator<Item = &'a str>,
+            {
+                let mut tokens = tokens.fuse();
+
+                parse_prefix!(tokens, 10, $len, $error_name; $($index, $success_type, $field_name, $error_variant;)*);
+                let $rest_name = RestOfLine::from_remaining(&mut tokens);
+
+                Ok(($($field_name,)* $rest_name,))
+            }
+
+            fn from_line_tokens<'a, I>(mut tokens: I) -> Result<Self, Self::Error>
+            where
+                I: Remainder<'a>,
+            {
+                // The empty tuple doesn't parse any elements.
+                #[allow(unused_variables)]
+                let radix = tokens.integer_radix();
+                parse_prefix!(tokens, radix, $len, $error_name; $($index, $success_type, $field_name, $error_variant;)*);
+                let $rest_name = RestOfLine(tokens.remainder().to_owned());
+
+                Ok(($($field_name,)* $rest_name,))
+            }
+        }
+    };
+}
+
 include!(concat!(env!("OUT_DIR"), "/tuple_calls.rs"));
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
-    use crate::{ParseTokenPatternError, ReadTokensError, TokenReader};
+    use crate::{Optional, ParseTokenPatternError, ReadTokensError, RestOfLine, TokenReader};
 
     #[test]
     fn reads_single_value() {
@@ -89,6 +188,43 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn reads_rest_of_line() {
+        let mut input = TokenReader::new("1 2  three four\n".as_bytes());
+        let (a, b, rest): (u8, u8, RestOfLine) = input.line().unwrap();
+
+        assert_eq!((a, b), (1, 2));
+        assert_eq!(rest.as_str(), "three four");
+    }
+
+    #[test]
+    fn reads_optional_values_before_rest_of_line() {
+        let mut input = TokenReader::new("1 2 three four\n1\n".as_bytes());
+
+        let (a, Optional(b), rest): (u32, Optional<u32>, RestOfLine) = input.line().unwrap();
+        assert_eq!((a, b, rest.as_str()), (1, Some(2), "three four"));
+
+        let (a, Optional(b), rest): (u32, Optional<u32>, RestOfLine) = input.line().unwrap();
+        assert_eq!((a, b, rest.as_str()), (1, None, ""));
+    }
+
+    #[test]
+    fn returns_error_on_missing_tokens_before_rest_of_line() {
+        let mut input = TokenReader::new("1".as_bytes());
+        let result = input.line::<(u8, u8, RestOfLine)>();
+
+        assert!(matches!(
+            result,
+            Err(ReadTokensError::ParseError {
+                source: ParseTokenPatternError::TooFewTokens {
+                    expected: 2,
+                    real: 1
+                },
+                ..
+            })
+        ));
+    }
+
     #[test]
     fn returns_error_on_too_few_elements() {
         let mut input = TokenReader::new("10".as_bytes());
@@ -0,0 +1,126 @@
+use core::{fmt, str::FromStr};
+
+use crate::{FromTokens, ParseIndex1Error, ParseTokenPatternError};
+
+/// A zero-based index that is written as a one-based number.
+///
+/// Parsing subtracts one from the number and rejects zero,
+/// while [`Display`](fmt::Display) adds one back, so answers can be printed the way they were read.
+/// It's implemented for all unsigned integers up to 64 bits.
+///
+/// A single index can be read from a line directly, or used as an element of a tuple or collection.
+///
+/// # Example
+///
+/// ```
+/// # use token_read::{Index1, SliceReader, Usize1};
+/// # use anyhow::Result;
+/// #
+/// # fn main() -> Result<()> {
+/// let mut input = SliceReader::new("1 3\n2");
+///
+/// let (Index1(from), Index1(to)): (Usize1, Usize1) = input.line()?;
+/// let start: Usize1 = input.line()?;
+///
+/// assert_eq!((from, to), (0, 2));
+/// assert_eq!(start.into_inner(), 1);
+/// assert_eq!(Index1(to).to_string(), "3");
+/// #
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Index1<T>(pub T);
+
+/// A zero-based [`usize`] index that is written as a one-based number.
+pub type Usize1 = Index1<usize>;
+
+impl<T> Index1<T> {
+    /// Returns the zero-based index.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+macro_rules! impl_index1 {
+    ($($t:ty)*) => {
+        $(
+            impl FromStr for Index1<$t> {
+                type Err = ParseIndex1Error;
+
+                fn from_str(token: &str) -> Result<Self, Self::Err> {
+                    match token.parse::<$t>() {
+                        Ok(0) => Err(ParseIndex1Error::Zero),
+                        Ok(number) => Ok(Index1(number - 1)),
+                        Err(source) => Err(ParseIndex1Error::ParseError { source }),
+                    }
+                }
+            }
+
+            impl fmt::Display for Index1<$t> {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    // Widened, so that the largest index can be displayed too.
+                    fmt::Display::fmt(&(self.0 as u128 + 1), f)
+                }
+            }
+        )*
+    };
+}
+
+impl_index1!(u8 u16 u32 u64 usize);
+
+impl<T> FromTokens for Index1<T>
+where
+    Index1<T>: FromStr<Err = ParseIndex1Error>,
+{
+    type Error = ParseTokenPatternError<ParseIndex1Error>;
+
+    fn from_tokens<'a, I>(tokens: I) -> Result<Self, Self::Error>
+    where
+        I: Iterator<Item = &'a str>,
+    {
+        let [index] = <[Self; 1]>::from_tokens(tokens)?;
+        Ok(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{format, string::ToString, vec::Vec};
+
+    use crate::{Index1, ParseIndex1Error, ParseTokenPatternError, ToTokens, Usize1};
+
+    #[test]
+    fn subtracts_one() {
+        assert_eq!("1".parse(), Ok(Index1(0usize)));
+        assert_eq!("255".parse(), Ok(Index1(254u8)));
+
+        let indices: Vec<Usize1> = "3 1 2".parse_tokens().unwrap();
+        assert_eq!(indices, [Index1(2), Index1(0), Index1(1)]);
+    }
+
+    #[test]
+    fn rejects_zero() {
+        assert_eq!("0".parse::<Usize1>(), Err(ParseIndex1Error::Zero));
+        assert!(matches!(
+            "-1".parse::<Usize1>(),
+            Err(ParseIndex1Error::ParseError { .. })
+        ));
+    }
+
+    #[test]
+    fn reads_single_index() {
+        assert_eq!("4".parse_tokens::<Index1<u32>>().unwrap(), Index1(3));
+        assert!(matches!(
+            "1 2".parse_tokens::<Usize1>(),
+            Err(ParseTokenPatternError::TooManyTokens { expected: 1 })
+        ));
+    }
+
+    #[test]
+    fn displays_one_based() {
+        assert_eq!(Index1(0usize).to_string(), "1");
+        assert_eq!(Index1(u64::MAX).to_string(), "18446744073709551616");
+        assert_eq!(format!("{:>3}", Index1(8u8)), "  9");
+    }
+}
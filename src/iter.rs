@@ -1,34 +1,34 @@
-use std::{io::BufRead, marker::PhantomData};
+use core::marker::PhantomData;
 
-use crate::{FromTokens, LineCount, ReadTokensError, TokenReader};
+use crate::{FromTokens, LineCount, ReadTokensError, TokenSource};
 
-/// An iterator returned from [`TokenReader::take`].
+/// An iterator returned from [`TokenSource::take`].
 #[derive(Debug)]
 pub struct Take<'a, T, R, S> {
-    reader: &'a mut TokenReader<R>,
+    reader: &'a mut R,
     remaining: S,
     _phantom: PhantomData<T>,
 }
 
 impl<'a, T, R, S> Take<'a, T, R, S>
 where
-    R: BufRead,
+    R: TokenSource,
     T: FromTokens,
     S: LineCount,
 {
-    /// Creates a new [`Take`] iterator. It's recommended to use [`TokenReader::take`] instead.
-    pub fn new(reader: &'a mut TokenReader<R>, count: S) -> Self {
+    /// Creates a new [`Take`] iterator. It's recommended to use [`TokenSource::take`] instead.
+    pub fn new(reader: &'a mut R, count: S) -> Self {
         Take {
             reader,
             remaining: count,
-            _phantom: PhantomData::default(),
+            _phantom: PhantomData,
         }
     }
 }
 
 impl<'a, T, R, S> Iterator for Take<'a, T, R, S>
 where
-    R: BufRead,
+    R: TokenSource,
     T: FromTokens,
     S: LineCount,
 {
@@ -0,0 +1,422 @@
+use alloc::{borrow::ToOwned, string::String, vec::Vec};
+use core::{convert::Infallible, str::FromStr};
+
+use crate::{
+    FromTokens, ParseFieldError, ParseKeyValueError, ParseRecordError, ParseToken, RecordError,
+};
+
+/// A token of the form `key=value`.
+///
+/// The token is split at the first `=` and the value is parsed using [`FromStr`].
+///
+/// # Example
+///
+/// ```
+/// # use token_read::{KeyValue, ToTokens};
+/// # use anyhow::Result;
+/// #
+/// # fn main() -> Result<()> {
+/// let (size,): (KeyValue<u32>,) = "n=5".parse_tokens()?;
+///
+/// assert_eq!(size.key, "n");
+/// assert_eq!(size.value, 5);
+/// #
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct KeyValue<T> {
+    pub key: String,
+    pub value: T,
+}
+
+impl<T: FromStr> FromStr for KeyValue<T> {
+    type Err = ParseKeyValueError<T::Err>;
+
+    fn from_str(token: &str) -> Result<Self, Self::Err> {
+        let (key, value) = token
+            .split_once('=')
+            .ok_or(ParseKeyValueError::MissingSeparator)?;
+
+        Ok(KeyValue {
+            key: key.to_owned(),
+            value: T::parse_token(value)
+                .map_err(|source| ParseKeyValueError::ParseError { source })?,
+        })
+    }
+}
+
+/// A line of `key=value` tokens, which can appear in any order.
+///
+/// The values are kept as strings and parsed when they are retrieved.
+/// Every key may only appear once.
+///
+/// A record can also be converted into a struct implementing [`FromRecord`],
+/// or with the `serde` feature deserialized into one,
+/// in both cases matching fields to keys by name.
+///
+/// # Example
+///
+/// ```
+/// # use token_read::{Record, SliceReader};
+/// # use anyhow::Result;
+/// #
+/// # fn main() -> Result<()> {
+/// let mut input = SliceReader::new("seed=42 n=5 m=10");
+/// let record: Record = input.line()?;
+///
+/// record.check_keys(&["n", "m", "seed", "name"])?;
+///
+/// let n: usize = record.get("n")?;
+/// let seed: u64 = record.get("seed")?;
+/// let name: Option<String> = record.get_optional("name")?;
+///
+/// assert_eq!((n, seed, name), (5, 42, None));
+/// #
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Record {
+    entries: Vec<(String, String)>,
+}
+
+impl Record {
+    /// Returns the unparsed value of a key.
+    pub fn get_raw(&self, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Parses the value of a key, which has to be present.
+    pub fn get<T>(&self, key: &str) -> Result<T, RecordError<T::Err>>
+    where
+        T: FromStr,
+    {
+        self.get_optional(key)?
+            .ok_or_else(|| RecordError::MissingKey {
+                key: key.to_owned(),
+            })
+    }
+
+    /// Parses the value of a key, returning [`None`] if it's not present.
+    pub fn get_optional<T>(&self, key: &str) -> Result<Option<T>, RecordError<T::Err>>
+    where
+        T: FromStr,
+    {
+        self.get_raw(key)
+            .map(|value| {
+                T::parse_token(value).map_err(|source| RecordError::ParseError {
+                    key: key.to_owned(),
+                    source,
+                })
+            })
+            .transpose()
+    }
+
+    /// Checks that the record contains no keys other than the allowed ones.
+    pub fn check_keys(&self, allowed: &[&str]) -> Result<(), RecordError<Infallible>> {
+        match self.keys().find(|key| !allowed.contains(key)) {
+            Some(key) => Err(RecordError::UnknownKey {
+                key: key.to_owned(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns an iterator over the keys, in the order they appeared in.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    /// Returns an iterator over the keys and unparsed values, in the order they appeared in.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    /// Returns the number of keys.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether the record contains no keys.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// A type that can be built from the keys of a [`Record`].
+///
+/// With the `derive` feature, it can be derived for structs with named fields.
+/// Every field is read from the key with the same name, which can be changed using `#[token(key = "...")]`.
+/// Fields of type [`Option`] may be missing, all other keys are required.
+/// Keys that don't belong to any field are rejected.
+///
+/// # Example
+///
+/// ```
+/// # use token_read::{FromRecord, Record, SliceReader};
+/// # use anyhow::Result;
+/// #
+/// # #[cfg(feature = "derive")]
+/// #[derive(FromRecord)]
+/// struct Test {
+///     n: usize,
+///     #[token(key = "seed")]
+///     random_seed: u64,
+///     name: Option<String>,
+/// }
+///
+/// # #[cfg(feature = "derive")]
+/// # fn main() -> Result<()> {
+/// let mut input = SliceReader::new("seed=42 n=5");
+/// let record: Record = input.line()?;
+/// let test = Test::from_record(&record)?;
+///
+/// assert_eq!(test.n, 5);
+/// assert_eq!(test.random_seed, 42);
+/// assert_eq!(test.name, None);
+/// #
+/// #   Ok(())
+/// # }
+/// # #[cfg(not(feature = "derive"))]
+/// # fn main() {}
+/// ```
+pub trait FromRecord: Sized {
+    /// Builds the value from the keys of a record.
+    fn from_record(record: &Record) -> Result<Self, RecordError<ParseFieldError>>;
+}
+
+impl FromTokens for Record {
+    type Error = ParseRecordError;
+
+    fn from_tokens<'a, I>(tokens: I) -> Result<Self, Self::Error>
+    where
+        I: Iterator<Item = &'a str>,
+    {
+        let mut record = Record::default();
+
+        for token in tokens {
+            let (key, value) =
+                token
+                    .split_once('=')
+                    .ok_or_else(|| ParseRecordError::MissingSeparator {
+                        token: token.to_owned(),
+                    })?;
+
+            if record.get_raw(key).is_some() {
+                return Err(ParseRecordError::DuplicateKey {
+                    key: key.to_owned(),
+                });
+            }
+
+            record.entries.push((key.to_owned(), value.to_owned()));
+        }
+
+        Ok(record)
+    }
+}
+
+#[cfg(feature = "serde")]
+mod deserialize {
+    use serde::de::DeserializeOwned;
+
+    use super::Record;
+    use crate::{de::NamedFields, DeserializeError};
+
+    impl Record {
+        /// Deserializes the record into a type using [serde](https://serde.rs), matching fields to keys by name.
+        ///
+        /// Keys can be renamed using `#[serde(rename = "...")]`.
+        /// Missing keys are errors unless the field is an [`Option`] or has a default value,
+        /// and unknown keys are errors if the type uses `#[serde(deny_unknown_fields)]`.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// # use token_read::{Record, ToTokens};
+        /// # use serde::Deserialize;
+        /// # use anyhow::Result;
+        /// #
+        /// #[derive(Deserialize)]
+        /// #[serde(deny_unknown_fields)]
+        /// struct Test {
+        ///     n: usize,
+        ///     #[serde(rename = "seed")]
+        ///     random_seed: u64,
+        ///     #[serde(default)]
+        ///     name: Option<String>,
+        /// }
+        ///
+        /// # fn main() -> Result<()> {
+        /// let record: Record = "seed=42 n=5".parse_tokens()?;
+        /// let test: Test = record.deserialize()?;
+        ///
+        /// assert_eq!(test.n, 5);
+        /// assert_eq!(test.random_seed, 42);
+        /// assert_eq!(test.name, None);
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        pub fn deserialize<T>(&self) -> Result<T, DeserializeError>
+        where
+            T: DeserializeOwned,
+        {
+            T::deserialize(&mut NamedFields::new(self.iter()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{borrow::ToOwned, string::String, vec::Vec};
+
+    use crate::{KeyValue, ParseKeyValueError, ParseRecordError, Record, RecordError, ToTokens};
+
+    #[test]
+    fn parses_key_values() {
+        let pairs: Vec<KeyValue<String>> = "a=1 b= c==3".parse_tokens().unwrap();
+        let pairs: Vec<(&str, &str)> = pairs
+            .iter()
+            .map(|pair| (pair.key.as_str(), pair.value.as_str()))
+            .collect();
+
+        assert_eq!(pairs, [("a", "1"), ("b", ""), ("c", "=3")]);
+    }
+
+    #[test]
+    fn rejects_key_values_without_separator() {
+        assert_eq!(
+            "a".parse::<KeyValue<String>>(),
+            Err(ParseKeyValueError::MissingSeparator)
+        );
+        assert!(matches!(
+            "a=x".parse::<KeyValue<u8>>(),
+            Err(ParseKeyValueError::ParseError { .. })
+        ));
+    }
+
+    #[test]
+    fn reads_record_values() {
+        let record: Record = "m=10 n=5".parse_tokens().unwrap();
+
+        assert_eq!(record.get::<u8>("n"), Ok(5));
+        assert_eq!(record.get_optional::<u8>("k"), Ok(None));
+        assert_eq!(
+            record.get::<u8>("k"),
+            Err(RecordError::MissingKey {
+                key: "k".to_owned()
+            })
+        );
+        assert!(matches!(
+            record.get::<bool>("m"),
+            Err(RecordError::ParseError { .. })
+        ));
+        assert_eq!(record.keys().collect::<Vec<_>>(), ["m", "n"]);
+    }
+
+    #[test]
+    fn rejects_invalid_records() {
+        assert_eq!(
+            "a=1 a=2".parse_tokens::<Record>(),
+            Err(ParseRecordError::DuplicateKey {
+                key: "a".to_owned()
+            })
+        );
+        assert_eq!(
+            "a=1 b".parse_tokens::<Record>(),
+            Err(ParseRecordError::MissingSeparator {
+                token: "b".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        let record: Record = "a=1 b=2".parse_tokens().unwrap();
+
+        assert_eq!(record.check_keys(&["a", "b", "c"]), Ok(()));
+        assert_eq!(
+            record.check_keys(&["a"]),
+            Err(RecordError::UnknownKey {
+                key: "b".to_owned()
+            })
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserializes_records() {
+        use serde::Deserialize;
+
+        #[derive(Deserialize, Debug, PartialEq)]
+        #[serde(deny_unknown_fields)]
+        struct Config {
+            n: u32,
+            #[serde(rename = "seed")]
+            random_seed: u64,
+        }
+
+        let record: Record = "seed=7 n=3".parse_tokens().unwrap();
+        assert_eq!(
+            record.deserialize::<Config>().unwrap(),
+            Config {
+                n: 3,
+                random_seed: 7
+            }
+        );
+
+        let record: Record = "n=3".parse_tokens().unwrap();
+        assert!(record.deserialize::<Config>().is_err());
+
+        let record: Record = "n=3 seed=1 m=2".parse_tokens().unwrap();
+        assert!(record.deserialize::<Config>().is_err());
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn reads_derived_records() {
+        use crate::FromRecord;
+
+        #[derive(FromRecord, Debug, PartialEq)]
+        struct Config {
+            n: u32,
+            #[token(key = "seed")]
+            random_seed: u64,
+            name: Option<String>,
+        }
+
+        let record: Record = "seed=7 n=3".parse_tokens().unwrap();
+        assert_eq!(
+            Config::from_record(&record).unwrap(),
+            Config {
+                n: 3,
+                random_seed: 7,
+                name: None
+            }
+        );
+
+        let record: Record = "n=3".parse_tokens().unwrap();
+        assert!(matches!(
+            Config::from_record(&record),
+            Err(RecordError::MissingKey { key }) if key == "seed"
+        ));
+
+        let record: Record = "n=3 seed=1 m=2".parse_tokens().unwrap();
+        assert!(matches!(
+            Config::from_record(&record),
+            Err(RecordError::UnknownKey { key }) if key == "m"
+        ));
+
+        let record: Record = "n=x seed=1".parse_tokens().unwrap();
+        assert!(matches!(
+            Config::from_record(&record),
+            Err(RecordError::ParseError { key, .. }) if key == "n"
+        ));
+    }
+}
@@ -0,0 +1,86 @@
+//! Reading and writing sequences prefixed by their length using serde.
+//!
+//! By default, sequences take up a whole line.
+//! Fields marked with `#[serde(with = "token_read::length_prefixed")]`
+//! instead start with a token containing the number of elements, which may then span multiple lines.
+//!
+//! When used with other formats, the field is serialized and deserialized normally.
+//!
+//! # Example
+//!
+//! ```
+//! # use serde::{Deserialize, Serialize};
+//! # use anyhow::Result;
+//! #
+//! #[derive(Deserialize, Serialize)]
+//! struct Test {
+//!     #[serde(with = "token_read::length_prefixed")]
+//!     values: Vec<u32>,
+//!     name: String,
+//! }
+//!
+//! # fn main() -> Result<()> {
+//! let test: Test = token_read::from_str("3\n10\n20\n30\nlast")?;
+//!
+//! assert_eq!(test.values, vec![10, 20, 30]);
+//! assert_eq!(test.name, "last");
+//! assert_eq!(token_read::to_string(&test)?, "3 10 20 30 last\n");
+//! #
+//! #   Ok(())
+//! # }
+//! ```
+
+use core::{fmt, marker::PhantomData};
+
+use serde::{
+    de::{self, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+/// The name of the newtype struct used to mark length-prefixed sequences.
+pub(crate) const NAME: &str = "$token_read::LengthPrefixed";
+
+/// Serializes a sequence prefixed by its length.
+pub fn serialize<S, T>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Serialize + ?Sized,
+{
+    serializer.serialize_newtype_struct(NAME, value)
+}
+
+/// Deserializes a sequence prefixed by its length.
+pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    struct LengthPrefixedVisitor<T>(PhantomData<T>);
+
+    impl<'de, T> Visitor<'de> for LengthPrefixedVisitor<T>
+    where
+        T: Deserialize<'de>,
+    {
+        type Value = T;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a sequence prefixed by its length")
+        }
+
+        fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            T::deserialize(deserializer)
+        }
+
+        fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: de::SeqAccess<'de>,
+        {
+            T::deserialize(de::value::SeqAccessDeserializer::new(seq))
+        }
+    }
+
+    deserializer.deserialize_newtype_struct(NAME, LengthPrefixedVisitor(PhantomData))
+}
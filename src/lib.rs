@@ -19,11 +19,14 @@
 //! ## Code
 //!
 //! ```no_run
+//! # #[cfg(feature = "std")]
 //! use std::io::stdin;
 //!
 //! use anyhow::Result;
+//! # #[cfg(feature = "std")]
 //! use token_read::TokenReader;
 //!
+//! # #[cfg(feature = "std")]
 //! fn main() -> Result<()> {
 //!     let mut input = TokenReader::new(stdin().lock());
 //!
@@ -36,27 +39,106 @@
 //!
 //!     Ok(())
 //! }
+//! # #[cfg(not(feature = "std"))]
+//! # fn main() {}
 //' ```
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+// Lets the tests use the derive macros, which refer to the crate by name.
+#[cfg(all(test, feature = "derive"))]
+extern crate self as token_read;
+
+#[cfg(any(feature = "futures", feature = "tokio"))]
+#[macro_use]
+mod async_reader;
+#[cfg(feature = "bigint")]
+pub mod bigint;
+mod bytes;
+mod canonical;
+mod chars;
 mod count;
+mod csv;
+#[cfg(feature = "serde")]
+mod de;
+mod decimal;
+mod delimiter;
 mod error;
+mod fast_parse;
+#[cfg(feature = "futures")]
+pub mod futures;
+#[cfg(feature = "std")]
+pub mod graph;
 pub mod impls;
+mod index;
 mod iter;
+mod key_value;
+#[cfg(feature = "serde")]
+pub mod length_prefixed;
+#[cfg(feature = "mmap")]
+mod mmap;
+mod optional;
+mod options;
+mod quoted;
+mod radix;
+#[cfg(feature = "std")]
 mod reader;
+mod rest_of_line;
+#[cfg(feature = "serde")]
+mod ser;
+#[cfg(all(test, feature = "std"))]
+mod shared_tests;
+mod slice;
+mod source;
+mod swar;
 mod to_tokens;
+#[cfg(feature = "tokio")]
+pub mod tokio;
 
+pub use bytes::{ByteTokens, FromByteTokens, FromBytes};
+pub use canonical::Canonical;
+pub use chars::{BitString, Bytes, Chars};
 pub use count::LineCount;
-pub use error::{ParseTokenPatternError, ReadLineError, ReadTokensError};
+pub use csv::{CsvError, CsvReader, CsvRows};
+#[cfg(feature = "serde")]
+pub use de::{from_str, DeserializeError, Deserializer};
+pub use decimal::Decimal;
+pub use delimiter::{Delimiter, Split, SplitTokens};
+pub use error::{
+    AlphabetError, ParseCanonicalError, ParseDecimalError, ParseFieldError, ParseIndex1Error,
+    ParseIntBytesError, ParseKeyValueError, ParseQuotedError, ParseRecordError,
+    ParseTokenPatternError, ReadLineError, ReadTokensError, RecordError,
+};
+pub use fast_parse::{FastParse, ParseToken};
+pub use index::{Index1, Usize1};
 pub use iter::Take;
+pub use key_value::{FromRecord, KeyValue, Record};
+#[cfg(feature = "mmap")]
+pub use mmap::MmapInput;
+pub use optional::{Optional, OrDefault, TupleElement};
+pub use options::ReaderOptions;
+pub use quoted::Quoted;
+pub use radix::{Bin, Hex, Oct, Radix};
+#[cfg(feature = "std")]
 pub use reader::TokenReader;
-pub use to_tokens::ToTokens;
+pub use rest_of_line::RestOfLine;
+#[cfg(feature = "serde")]
+pub use ser::{to_string, SerializeError, Serializer};
+pub use slice::SliceReader;
+pub use source::TokenSource;
+pub use to_tokens::{Remainder, ToTokens, Tokens};
+#[cfg(feature = "derive")]
+pub use token_read_derive::FromRecord;
 
 #[cfg(doc)]
-use std::str::FromStr;
+use core::str::FromStr;
 
 /// A trait for types parsable from an iterator of whitespace delimited tokens.
 ///
 /// Implementations are provided for tuples and collections of types implementing [`FromStr`].
+/// The individual tokens are parsed using [`ParseToken`].
+/// The last element of a tuple can also be a [`RestOfLine`].
 pub trait FromTokens: Sized {
     type Error;
 
@@ -64,4 +146,15 @@ pub trait FromTokens: Sized {
     fn from_tokens<'a, I>(tokens: I) -> Result<Self, Self::Error>
     where
         I: Iterator<Item = &'a str>;
+
+    /// Parses a value from an iterator of tokens which can also provide the rest of the line.
+    ///
+    /// It's used when reading lines and by [`ToTokens::parse_tokens`] on strings.
+    /// The default implementation calls [`FromTokens::from_tokens`].
+    fn from_line_tokens<'a, I>(tokens: I) -> Result<Self, Self::Error>
+    where
+        I: Remainder<'a>,
+    {
+        Self::from_tokens(tokens)
+    }
 }
@@ -0,0 +1,148 @@
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, Cursor, Read},
+    path::Path,
+};
+
+use memmap2::Mmap;
+
+use crate::TokenReader;
+
+/// An input backed either by a memory mapped file or by a buffered reader.
+///
+/// It's created by [`TokenReader::from_mmap`].
+#[derive(Debug)]
+pub struct MmapInput {
+    inner: MmapInputInner,
+}
+
+#[derive(Debug)]
+enum MmapInputInner {
+    Mapped(Cursor<Mmap>),
+    Buffered(BufReader<File>),
+}
+
+impl MmapInput {
+    /// Returns whether the file was successfully mapped into memory.
+    pub fn is_mapped(&self) -> bool {
+        matches!(self.inner, MmapInputInner::Mapped(_))
+    }
+}
+
+impl Read for MmapInput {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match &mut self.inner {
+            MmapInputInner::Mapped(cursor) => cursor.read(buf),
+            MmapInputInner::Buffered(reader) => reader.read(buf),
+        }
+    }
+}
+
+impl BufRead for MmapInput {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        match &mut self.inner {
+            MmapInputInner::Mapped(cursor) => cursor.fill_buf(),
+            MmapInputInner::Buffered(reader) => reader.fill_buf(),
+        }
+    }
+
+    fn consume(&mut self, amt: usize) {
+        match &mut self.inner {
+            MmapInputInner::Mapped(cursor) => cursor.consume(amt),
+            MmapInputInner::Buffered(reader) => reader.consume(amt),
+        }
+    }
+}
+
+impl TokenReader<MmapInput> {
+    /// Creates a [`TokenReader`] that reads a file by mapping it into memory.
+    ///
+    /// The whole file is then visible as a single buffer,
+    /// so lines are tokenized in place without being copied.
+    /// If the path doesn't point to a regular file, for example if it's a pipe or `/dev/stdin`,
+    /// or if mapping fails, the file is read using a [`BufReader`] instead.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated, even by other processes,
+    /// until the reader is dropped. See [`Mmap::map`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use token_read::TokenReader;
+    /// # use anyhow::Result;
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let mut input = unsafe { TokenReader::from_mmap("input.txt")? };
+    /// let (n,): (usize,) = input.line()?;
+    /// #
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub unsafe fn from_mmap<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = File::open(path)?;
+        let metadata = file.metadata()?;
+
+        // Empty files can't be mapped on some platforms.
+        if metadata.is_file() && metadata.len() > 0 {
+            if let Ok(map) = Mmap::map(&file) {
+                return Ok(TokenReader::new(MmapInput {
+                    inner: MmapInputInner::Mapped(Cursor::new(map)),
+                }));
+            }
+        }
+
+        Ok(TokenReader::new(MmapInput {
+            inner: MmapInputInner::Buffered(BufReader::new(file)),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, path::PathBuf, process};
+
+    use crate::{ReadLineError, TokenReader};
+
+    fn temp_file(name: &str, content: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("token-read-{}-{name}", process::id()));
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn reads_mapped_file() {
+        let path = temp_file("mapped", "2\n1 a\r\n2 b\nend");
+        let mut input = unsafe { TokenReader::from_mmap(&path).unwrap() };
+        assert!(input.get_ref().is_mapped());
+
+        let (count,): (usize,) = input.line().unwrap();
+        let lines: Vec<(u8, char)> = input.take(count).collect::<Result<_, _>>().unwrap();
+        let end = input.line_raw().unwrap();
+
+        assert_eq!(lines, vec![(1, 'a'), (2, 'b')]);
+        assert_eq!(end, "end");
+        assert!(matches!(input.line_raw(), Err(ReadLineError::EndOfFile)));
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn reads_empty_file() {
+        let path = temp_file("empty", "");
+        let mut input = unsafe { TokenReader::from_mmap(&path).unwrap() };
+
+        assert!(matches!(input.line_raw(), Err(ReadLineError::EndOfFile)));
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn falls_back_for_special_files() {
+        let mut input = unsafe { TokenReader::from_mmap("/dev/null").unwrap() };
+        assert!(!input.get_ref().is_mapped());
+        assert!(matches!(input.line_raw(), Err(ReadLineError::EndOfFile)));
+    }
+}
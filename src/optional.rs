@@ -0,0 +1,222 @@
+use core::str::FromStr;
+
+use crate::ParseToken;
+
+#[cfg(doc)]
+use crate::{FromTokens, ParseTokenPatternError};
+
+/// A trait for types that can be elements of tuples parsed using [`FromTokens`].
+///
+/// It's implemented for all types implementing [`FromStr`], which are required,
+/// and for [`Optional`] and [`OrDefault`], which may be missing at the end of the line.
+///
+/// Only trailing elements may be optional. Parsing a tuple with a required element
+/// after an optional one fails to compile.
+///
+/// ```compile_fail
+/// # use token_read::{Optional, ToTokens};
+/// let result = "1 2".parse_tokens::<(Optional<u8>, u8)>();
+/// ```
+pub trait TupleElement: Sized {
+    type Err;
+
+    /// Whether the element can be missing.
+    const OPTIONAL: bool = false;
+
+    /// Parses the element from a token, reading primitive integers in the given radix.
+    fn parse_element(token: &str, radix: u32) -> Result<Self, Self::Err>;
+
+    /// Returns the value used when the token is missing, or [`None`] if the element is required.
+    fn missing_element() -> Option<Self> {
+        None
+    }
+}
+
+impl<T: FromStr> TupleElement for T {
+    type Err = T::Err;
+
+    fn parse_element(token: &str, radix: u32) -> Result<Self, Self::Err> {
+        T::parse_token_in_radix(token, radix)
+    }
+}
+
+/// A tuple element that is [`None`] if the line has no more tokens.
+///
+/// [`Option`] itself can't be used. Every type implementing [`FromStr`] is already a [`TupleElement`],
+/// and as the standard library is allowed to implement [`FromStr`] for [`Option`] in the future,
+/// the compiler rejects a separate [`TupleElement`] implementation for [`Option`].
+///
+/// ```compile_fail
+/// # use token_read::ToTokens;
+/// let result = "1 2".parse_tokens::<(u8, Option<u8>)>();
+/// ```
+///
+/// # Example
+///
+/// ```
+/// # use token_read::{Optional, ToTokens};
+/// # use anyhow::Result;
+/// #
+/// # fn main() -> Result<()> {
+/// let (u, v, Optional(w)): (usize, usize, Optional<u64>) = "1 2".parse_tokens()?;
+/// assert_eq!(w, None);
+///
+/// let (u, v, Optional(w)): (usize, usize, Optional<u64>) = "1 2 30".parse_tokens()?;
+/// assert_eq!(w, Some(30));
+/// #
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Optional<T>(pub Option<T>);
+
+impl<T> Optional<T> {
+    /// Returns the wrapped value.
+    pub fn into_inner(self) -> Option<T> {
+        self.0
+    }
+}
+
+impl<T: FromStr> TupleElement for Optional<T> {
+    type Err = T::Err;
+
+    const OPTIONAL: bool = true;
+
+    fn parse_element(token: &str, radix: u32) -> Result<Self, Self::Err> {
+        T::parse_token_in_radix(token, radix).map(|value| Optional(Some(value)))
+    }
+
+    fn missing_element() -> Option<Self> {
+        Some(Optional(None))
+    }
+}
+
+/// A tuple element that takes its default value if the line has no more tokens.
+///
+/// Values other than [`Default::default`] can be set by implementing [`Default`] for a newtype.
+///
+/// # Example
+///
+/// ```
+/// # use token_read::{OrDefault, ToTokens};
+/// # use anyhow::Result;
+/// #
+/// # fn main() -> Result<()> {
+/// let (name, OrDefault(count)): (String, OrDefault<u32>) = "apples".parse_tokens()?;
+/// assert_eq!(count, 0);
+/// #
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OrDefault<T>(pub T);
+
+impl<T> OrDefault<T> {
+    /// Returns the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: FromStr + Default> TupleElement for OrDefault<T> {
+    type Err = T::Err;
+
+    const OPTIONAL: bool = true;
+
+    fn parse_element(token: &str, radix: u32) -> Result<Self, Self::Err> {
+        T::parse_token_in_radix(token, radix).map(OrDefault)
+    }
+
+    fn missing_element() -> Option<Self> {
+        Some(OrDefault(T::default()))
+    }
+}
+
+/// Checks that no required element follows an optional one.
+pub(crate) const fn only_trailing_optional(optional: &[bool]) -> bool {
+    let mut index = 1;
+
+    while index < optional.len() {
+        if optional[index - 1] && !optional[index] {
+            return false;
+        }
+
+        index += 1;
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+
+    use crate::{Optional, OrDefault, ParseTokenPatternError, ToTokens};
+
+    #[derive(Debug, PartialEq)]
+    struct Weight(u32);
+
+    impl Default for Weight {
+        fn default() -> Self {
+            Weight(1)
+        }
+    }
+
+    impl core::str::FromStr for Weight {
+        type Err = core::num::ParseIntError;
+
+        fn from_str(token: &str) -> Result<Self, Self::Err> {
+            token.parse().map(Weight)
+        }
+    }
+
+    #[test]
+    fn fills_missing_trailing_elements() {
+        let value: (u8, Optional<u8>, OrDefault<Weight>) = "1".parse_tokens().unwrap();
+        assert_eq!(value, (1, Optional(None), OrDefault(Weight(1))));
+
+        let value: (u8, Optional<u8>, OrDefault<Weight>) = "1 2 3".parse_tokens().unwrap();
+        assert_eq!(value, (1, Optional(Some(2)), OrDefault(Weight(3))));
+    }
+
+    #[test]
+    fn requires_leading_elements() {
+        let result = "".parse_tokens::<(String, Optional<u8>)>();
+
+        assert!(matches!(
+            result,
+            Err(ParseTokenPatternError::TooFewTokens {
+                real: 0,
+                expected: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn rejects_extra_tokens() {
+        let result = "1 2 3".parse_tokens::<(u8, Optional<u8>)>();
+
+        assert!(matches!(
+            result,
+            Err(ParseTokenPatternError::TooManyTokens { expected: 2 })
+        ));
+    }
+
+    #[test]
+    fn reports_parse_errors_of_optional_elements() {
+        let result = "1 x".parse_tokens::<(u8, OrDefault<u8>)>();
+        assert!(matches!(
+            result,
+            Err(ParseTokenPatternError::ParseError { .. })
+        ));
+    }
+
+    #[test]
+    fn checks_only_trailing_elements_are_optional() {
+        use super::only_trailing_optional;
+
+        assert!(only_trailing_optional(&[]));
+        assert!(only_trailing_optional(&[false, true, true]));
+        assert!(!only_trailing_optional(&[true, false]));
+    }
+}
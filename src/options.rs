@@ -0,0 +1,275 @@
+use alloc::{borrow::ToOwned, string::String};
+
+use crate::Delimiter;
+
+#[cfg(all(doc, feature = "std"))]
+use crate::TokenReader;
+#[cfg(doc)]
+use crate::{Bin, Hex, Oct, Radix, SliceReader, TokenSource};
+
+#[cfg_attr(
+    feature = "std",
+    doc = "Options controlling which lines a [`TokenReader`] or a [`SliceReader`] returns."
+)]
+#[cfg_attr(
+    not(feature = "std"),
+    doc = "Options controlling which lines a [`SliceReader`] returns."
+)]
+///
+/// By default, every line is returned unmodified.
+/// Skipped lines still count towards line numbers, so errors point to the right place in the input.
+///
+/// # Example
+///
+/// ```
+/// # use token_read::{ReaderOptions, SliceReader};
+/// # use anyhow::Result;
+/// #
+/// # fn main() -> Result<()> {
+/// let options = ReaderOptions::new()
+///     .skip_blank_lines(true)
+///     .comment_prefix("#")
+///     .trailing_comment("//");
+///
+/// let mut input = SliceReader::new("# size\n2\n\n1 2 // values").with_options(options);
+///
+/// let (n,): (usize,) = input.line()?;
+/// let values: Vec<u32> = input.line()?;
+///
+/// assert_eq!(n, 2);
+/// assert_eq!(values, vec![1, 2]);
+/// #
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ReaderOptions {
+    skip_blank_lines: bool,
+    comment_prefix: Option<String>,
+    trailing_comment: Option<String>,
+    delimiter: Delimiter,
+    integer_radix: u32,
+}
+
+impl ReaderOptions {
+    /// Creates the default options.
+    pub fn new() -> Self {
+        ReaderOptions {
+            skip_blank_lines: false,
+            comment_prefix: None,
+            trailing_comment: None,
+            delimiter: Delimiter::whitespace(),
+            integer_radix: 10,
+        }
+    }
+
+    /// Sets whether lines consisting only of whitespace are skipped.
+    ///
+    /// This includes lines that only become blank after a trailing comment is removed.
+    pub fn skip_blank_lines(mut self, skip: bool) -> Self {
+        self.skip_blank_lines = skip;
+        self
+    }
+
+    /// Skips lines starting with the given prefix, optionally preceded by whitespace.
+    ///
+    /// An empty prefix turns this off.
+    pub fn comment_prefix(mut self, prefix: &str) -> Self {
+        self.comment_prefix = (!prefix.is_empty()).then(|| prefix.to_owned());
+        self
+    }
+
+    /// Removes everything from the first occurrence of the given marker until the end of the line.
+    ///
+    /// Whitespace before the marker is removed as well.
+    /// The marker is found anywhere in the line, even inside a quoted token.
+    /// An empty marker turns this off.
+    pub fn trailing_comment(mut self, marker: &str) -> Self {
+        self.trailing_comment = (!marker.is_empty()).then(|| marker.to_owned());
+        self
+    }
+
+    /// Sets the delimiter used to split lines into tokens.
+    ///
+    /// It's used by [`TokenSource::line`] and [`TokenSource::take`], as well as when peeking at tokens.
+    /// Byte lines are always split at whitespace.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use token_read::{Delimiter, ReaderOptions, SliceReader};
+    /// # use anyhow::Result;
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let options = ReaderOptions::new().delimiter(Delimiter::chars(","));
+    /// let mut input = SliceReader::new("Alice Smith,42").with_options(options);
+    ///
+    /// let (name, age): (String, u8) = input.line()?;
+    ///
+    /// assert_eq!(name, "Alice Smith");
+    /// assert_eq!(age, 42);
+    /// #
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn delimiter(mut self, delimiter: Delimiter) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Sets the radix of primitive integers read by [`TokenSource::line`] and [`TokenSource::take`].
+    ///
+    /// It applies to integers that are elements of tuples, arrays and collections.
+    /// Tokens of other types, such as strings, are parsed as usual, and so are wrappers like
+    /// [`Hex`], [`Bin`], [`Oct`] and [`Radix`], which always use their own radix.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the radix isn't in the range from 2 to 36.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use token_read::{ReaderOptions, SliceReader};
+    /// # use anyhow::Result;
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let options = ReaderOptions::new().integer_radix(16);
+    /// let mut input = SliceReader::new("cafe ff\nde ad be ef").with_options(options);
+    ///
+    /// let (word, value): (String, u8) = input.line()?;
+    /// let bytes: Vec<u8> = input.line()?;
+    ///
+    /// assert_eq!((word.as_str(), value), ("cafe", 255));
+    /// assert_eq!(bytes, [0xde, 0xad, 0xbe, 0xef]);
+    /// #
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn integer_radix(mut self, radix: u32) -> Self {
+        assert!(
+            (2..=36).contains(&radix),
+            "radix must be in the range from 2 to 36, got {radix}"
+        );
+
+        self.integer_radix = radix;
+        self
+    }
+
+    /// Returns the radix of primitive integer tokens.
+    pub(crate) fn get_integer_radix(&self) -> u32 {
+        self.integer_radix
+    }
+
+    /// Returns the delimiter used to split lines into tokens.
+    pub(crate) fn get_delimiter(&self) -> &Delimiter {
+        &self.delimiter
+    }
+
+    /// Returns the length of the part of the line that should be kept,
+    /// or [`None`] if the line should be skipped.
+    pub(crate) fn kept_length(&self, line: &[u8]) -> Option<usize> {
+        if let Some(prefix) = &self.comment_prefix {
+            if line.trim_ascii_start().starts_with(prefix.as_bytes()) {
+                return None;
+            }
+        }
+
+        let line = match &self.trailing_comment {
+            Some(marker) => {
+                let start = find(line, marker.as_bytes()).unwrap_or(line.len());
+                line[..start].trim_ascii_end()
+            }
+            None => line,
+        };
+
+        if self.skip_blank_lines && line.trim_ascii_start().is_empty() {
+            return None;
+        }
+
+        Some(line.len())
+    }
+}
+
+impl Default for ReaderOptions {
+    fn default() -> Self {
+        ReaderOptions::new()
+    }
+}
+
+/// Finds the first occurrence of a non-empty needle in a byte slice.
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ReaderOptions;
+
+    #[test]
+    fn keeps_everything_by_default() {
+        let options = ReaderOptions::new();
+
+        assert_eq!(options.kept_length(b""), Some(0));
+        assert_eq!(options.kept_length(b"# 1 // 2"), Some(8));
+    }
+
+    #[test]
+    fn ignores_empty_comment_markers() {
+        let options = ReaderOptions::new()
+            .comment_prefix("#")
+            .trailing_comment("//")
+            .comment_prefix("")
+            .trailing_comment("");
+
+        assert_eq!(options.kept_length(b"# 1 // 2"), Some(8));
+        assert_eq!(options.kept_length(b""), Some(0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_invalid_radix() {
+        let _ = ReaderOptions::new().integer_radix(37);
+    }
+
+    #[test]
+    fn skips_blank_lines() {
+        let options = ReaderOptions::new().skip_blank_lines(true);
+
+        assert_eq!(options.kept_length(b""), None);
+        assert_eq!(options.kept_length(b" \t "), None);
+        assert_eq!(options.kept_length(b" 1 "), Some(3));
+    }
+
+    #[test]
+    fn skips_comment_lines() {
+        let options = ReaderOptions::new().comment_prefix("#");
+
+        assert_eq!(options.kept_length(b"# comment"), None);
+        assert_eq!(options.kept_length(b"  #comment"), None);
+        assert_eq!(options.kept_length(b"1 # 2"), Some(5));
+        assert_eq!(options.kept_length(b""), Some(0));
+    }
+
+    #[test]
+    fn strips_trailing_comments() {
+        let options = ReaderOptions::new().trailing_comment("//");
+
+        assert_eq!(options.kept_length(b"1 2 // three"), Some(3));
+        assert_eq!(options.kept_length(b"1 / 2"), Some(5));
+        assert_eq!(options.kept_length(b"// only"), Some(0));
+        assert_eq!(options.kept_length(b"\"a // b\" c"), Some(2));
+    }
+
+    #[test]
+    fn skips_lines_blank_after_stripping() {
+        let options = ReaderOptions::new()
+            .skip_blank_lines(true)
+            .trailing_comment("//");
+
+        assert_eq!(options.kept_length(b"  // only"), None);
+        assert_eq!(options.kept_length(b"1// one"), Some(1));
+    }
+}
@@ -0,0 +1,196 @@
+use alloc::string::String;
+use core::{
+    fmt::{self, Display, Write},
+    str::FromStr,
+};
+
+use crate::{delimiter::quoted_length, ParseQuotedError};
+
+#[cfg(doc)]
+use crate::Delimiter;
+
+/// A value that can be written as a double-quoted token, such as `"New York"`.
+///
+/// Quoted tokens can contain whitespace and the escape sequences `\"`, `\\`, `\n`, `\r` and `\t`.
+/// The content is unescaped and then parsed using [`FromStr`].
+/// Tokens that don't start with a quote are parsed as they are.
+///
+/// Quoted tokens containing whitespace have to be split using [`Delimiter::quoted_whitespace`].
+/// The [`Display`] implementation always writes the value quoted.
+///
+/// # Example
+///
+/// ```
+/// # use token_read::{Quoted, ToTokens};
+/// # use anyhow::Result;
+/// #
+/// # fn main() -> Result<()> {
+/// let (Quoted(a), Quoted(b)): (Quoted<String>, Quoted<String>) = r#""a\"b" c"#.parse_tokens()?;
+///
+/// assert_eq!(a, "a\"b");
+/// assert_eq!(b, "c");
+/// assert_eq!(Quoted("New York").to_string(), r#""New York""#);
+/// #
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Quoted<T>(pub T);
+
+impl<T> Quoted<T> {
+    /// Returns the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: FromStr> FromStr for Quoted<T> {
+    type Err = ParseQuotedError<T::Err>;
+
+    fn from_str(token: &str) -> Result<Self, Self::Err> {
+        let parse = |content: &str| {
+            content
+                .parse()
+                .map(Quoted)
+                .map_err(|source| ParseQuotedError::ParseError { source })
+        };
+
+        let Some(content) = token.strip_prefix('"') else {
+            return parse(token);
+        };
+
+        match quoted_length(token) {
+            Some(length) if length == token.len() => {}
+            Some(_) => return Err(ParseQuotedError::TrailingCharacters),
+            None => return Err(ParseQuotedError::Unterminated),
+        }
+
+        let content = &content[..content.len() - 1];
+
+        if !content.contains('\\') {
+            return parse(content);
+        }
+
+        let mut unescaped = String::with_capacity(content.len());
+        let mut chars = content.chars();
+
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                unescaped.push(c);
+                continue;
+            }
+
+            unescaped.push(match chars.next() {
+                Some('"') => '"',
+                Some('\\') => '\\',
+                Some('n') => '\n',
+                Some('r') => '\r',
+                Some('t') => '\t',
+                Some(escape) => return Err(ParseQuotedError::InvalidEscape { escape }),
+                None => unreachable!("the closing quote can't be escaped"),
+            });
+        }
+
+        parse(&unescaped)
+    }
+}
+
+impl<T: Display> Display for Quoted<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_char('"')?;
+        write!(Escaper(f), "{}", self.0)?;
+        f.write_char('"')
+    }
+}
+
+/// Escapes everything that is written to it.
+struct Escaper<'a, 'b>(&'a mut fmt::Formatter<'b>);
+
+impl Write for Escaper<'_, '_> {
+    fn write_str(&mut self, string: &str) -> fmt::Result {
+        for c in string.chars() {
+            match c {
+                '"' => self.0.write_str("\\\"")?,
+                '\\' => self.0.write_str("\\\\")?,
+                '\n' => self.0.write_str("\\n")?,
+                '\r' => self.0.write_str("\\r")?,
+                '\t' => self.0.write_str("\\t")?,
+                c => self.0.write_char(c)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{
+        string::{String, ToString},
+        vec::Vec,
+    };
+
+    use proptest::prelude::*;
+
+    use crate::{Delimiter, ParseQuotedError, Quoted, Split, ToTokens};
+
+    #[test]
+    fn parses_quoted_tokens() {
+        assert_eq!("\"a b\"".parse(), Ok(Quoted(String::from("a b"))));
+        assert_eq!("\"\"".parse(), Ok(Quoted(String::new())));
+        assert_eq!("\"12\"".parse(), Ok(Quoted(12u8)));
+    }
+
+    #[test]
+    fn parses_unquoted_tokens() {
+        assert_eq!("a\"b".parse(), Ok(Quoted(String::from("a\"b"))));
+    }
+
+    #[test]
+    fn unescapes_quoted_tokens() {
+        assert_eq!(
+            r#""\"\\\n\r\t""#.parse(),
+            Ok(Quoted(String::from("\"\\\n\r\t")))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_tokens() {
+        let parse = |token: &str| token.parse::<Quoted<String>>();
+
+        assert_eq!(parse("\"a"), Err(ParseQuotedError::Unterminated));
+        assert_eq!(parse("\"a\\\""), Err(ParseQuotedError::Unterminated));
+        assert_eq!(parse("\"a\"b"), Err(ParseQuotedError::TrailingCharacters));
+        assert_eq!(
+            parse("\"\\x\""),
+            Err(ParseQuotedError::InvalidEscape { escape: 'x' })
+        );
+    }
+
+    #[test]
+    fn reports_parse_errors() {
+        assert!(matches!(
+            "\"x\"".parse::<Quoted<u8>>(),
+            Err(ParseQuotedError::ParseError { .. })
+        ));
+    }
+
+    #[test]
+    fn displays_quoted_values() {
+        assert_eq!(Quoted("a \"b\"\n").to_string(), r#""a \"b\"\n""#);
+        assert_eq!(Quoted(5).to_string(), "\"5\"");
+    }
+
+    proptest! {
+        #[test]
+        fn round_trips_through_tokens(values: Vec<String>) {
+            let line = values.iter().map(|value| Quoted(value).to_string()).collect::<Vec<_>>().join(" ");
+            let delimiter = Delimiter::quoted_whitespace();
+
+            let parsed: Vec<Quoted<String>> = Split::new(&line, &delimiter).parse_tokens().unwrap();
+            let parsed: Vec<String> = parsed.into_iter().map(Quoted::into_inner).collect();
+
+            prop_assert_eq!(parsed, values);
+        }
+    }
+}
@@ -0,0 +1,376 @@
+use core::{
+    fmt::{self, Write},
+    num::{IntErrorKind, ParseIntError},
+    str::FromStr,
+};
+
+use arrayvec::ArrayString;
+
+#[cfg(doc)]
+use crate::ReaderOptions;
+
+/// A hexadecimal integer, such as `ff` or `0x1F`.
+///
+/// The `0x` prefix is optional and the sign comes before it.
+/// [`Display`](fmt::Display) writes the value in lowercase hexadecimal, with the prefix if the `#` flag is used.
+///
+/// # Example
+///
+/// ```
+/// # use token_read::{Hex, ToTokens};
+/// # use anyhow::Result;
+/// #
+/// # fn main() -> Result<()> {
+/// let bytes: Vec<Hex<u8>> = "de ad be ef".parse_tokens()?;
+/// let (Hex(address),): (Hex<u32>,) = "0x1F".parse_tokens()?;
+///
+/// assert_eq!(bytes[0], Hex(0xde));
+/// assert_eq!(address, 31);
+/// assert_eq!(format!("{:#}", Hex(address)), "0x1f");
+/// #
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Hex<T>(pub T);
+
+/// A binary integer, such as `101` or `0b101`.
+///
+/// The `0b` prefix is optional and the sign comes before it.
+/// [`Display`](fmt::Display) writes the value in binary, with the prefix if the `#` flag is used.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bin<T>(pub T);
+
+/// An octal integer, such as `755` or `0o755`.
+///
+/// The `0o` prefix is optional and the sign comes before it.
+/// [`Display`](fmt::Display) writes the value in octal, with the prefix if the `#` flag is used.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Oct<T>(pub T);
+
+/// An integer whose radix is given by its prefix: `0x` for hexadecimal, `0b` for binary,
+/// `0o` for octal and none for decimal.
+///
+/// To read all primitive integers of a line in a fixed radix, use [`ReaderOptions::integer_radix`].
+///
+/// # Example
+///
+/// ```
+/// # use token_read::{Radix, ToTokens};
+/// # use anyhow::Result;
+/// #
+/// # fn main() -> Result<()> {
+/// let values: Vec<Radix<i32>> = "0x10 -0b11 0o10 10".parse_tokens()?;
+///
+/// assert_eq!(values, [Radix(16), Radix(-3), Radix(8), Radix(10)]);
+/// #
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Radix<T>(pub T);
+
+macro_rules! impl_into_inner {
+    ($($name:ident)*) => {
+        $(
+            impl<T> $name<T> {
+                /// Returns the wrapped value.
+                pub fn into_inner(self) -> T {
+                    self.0
+                }
+            }
+        )*
+    };
+}
+
+impl_into_inner!(Hex Bin Oct Radix);
+
+/// An integer type that can be parsed in any radix.
+trait Integer: Sized {
+    /// Parses the digits of a nonnegative value, like `from_str_radix`.
+    fn from_digits(digits: &str, radix: u32) -> Result<Self, ParseIntError>;
+
+    /// Parses the digits of the magnitude of a negative value.
+    fn from_negative_digits(digits: &str, radix: u32) -> Result<Self, ParseIntError>;
+}
+
+macro_rules! impl_integer {
+    (unsigned: $($t:ty)*) => {
+        $(
+            impl Integer for $t {
+                fn from_digits(digits: &str, radix: u32) -> Result<Self, ParseIntError> {
+                    <$t>::from_str_radix(digits, radix)
+                }
+
+                fn from_negative_digits(_digits: &str, radix: u32) -> Result<Self, ParseIntError> {
+                    // Unsigned integers reject any minus sign, even before zero, as an invalid digit.
+                    <$t>::from_str_radix("-", radix)
+                }
+            }
+        )*
+    };
+    (signed: $($t:ty => $u:ty)*) => {
+        $(
+            impl Integer for $t {
+                fn from_digits(digits: &str, radix: u32) -> Result<Self, ParseIntError> {
+                    <$t>::from_str_radix(digits, radix)
+                }
+
+                fn from_negative_digits(digits: &str, radix: u32) -> Result<Self, ParseIntError> {
+                    let negative_overflow = || {
+                        // The magnitude of this number doesn't fit into any integer type.
+                        <$t>::from_str_radix("-100000000000000000000000000000000", 16).unwrap_err()
+                    };
+
+                    let magnitude = <$u>::from_str_radix(digits, radix).map_err(|error| {
+                        match error.kind() {
+                            IntErrorKind::PosOverflow => negative_overflow(),
+                            _ => error,
+                        }
+                    })?;
+
+                    <$t>::checked_sub_unsigned(0, magnitude).ok_or_else(negative_overflow)
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(unsigned: u8 u16 u32 u64 u128 usize);
+impl_integer!(signed: i8 => u8 i16 => u16 i32 => u32 i64 => u64 i128 => u128 isize => usize);
+
+/// Parses an integer in the given radix, which may be preceded by a sign and the prefix.
+fn parse_with_prefix<T: Integer>(
+    token: &str,
+    radix: u32,
+    prefix: &[&str],
+) -> Result<T, ParseIntError> {
+    let (negative, unsigned) = match token.strip_prefix('-') {
+        Some(unsigned) => (true, unsigned),
+        None => (false, token.strip_prefix('+').unwrap_or(token)),
+    };
+
+    let Some(digits) = prefix
+        .iter()
+        .find_map(|prefix| unsigned.strip_prefix(prefix))
+    else {
+        return T::from_digits(token, radix);
+    };
+
+    if digits.starts_with(['+', '-']) {
+        // A sign after the prefix is invalid, which is reported as the prefix being an invalid digit.
+        return T::from_digits(unsigned, radix);
+    }
+
+    if negative {
+        T::from_negative_digits(digits, radix)
+    } else {
+        T::from_digits(digits, radix)
+    }
+}
+
+macro_rules! impl_radix {
+    ($($t:ty)*) => {
+        $(
+            impl FromStr for Hex<$t> {
+                type Err = ParseIntError;
+
+                fn from_str(token: &str) -> Result<Self, Self::Err> {
+                    parse_with_prefix(token, 16, &["0x", "0X"]).map(Hex)
+                }
+            }
+
+            impl FromStr for Bin<$t> {
+                type Err = ParseIntError;
+
+                fn from_str(token: &str) -> Result<Self, Self::Err> {
+                    parse_with_prefix(token, 2, &["0b", "0B"]).map(Bin)
+                }
+            }
+
+            impl FromStr for Oct<$t> {
+                type Err = ParseIntError;
+
+                fn from_str(token: &str) -> Result<Self, Self::Err> {
+                    parse_with_prefix(token, 8, &["0o", "0O"]).map(Oct)
+                }
+            }
+
+            impl FromStr for Radix<$t> {
+                type Err = ParseIntError;
+
+                fn from_str(token: &str) -> Result<Self, Self::Err> {
+                    let unsigned = token.trim_start_matches(['+', '-']);
+
+                    let radix = match unsigned.get(..2) {
+                        Some("0x" | "0X") => 16,
+                        Some("0b" | "0B") => 2,
+                        Some("0o" | "0O") => 8,
+                        _ => return token.parse().map(Radix),
+                    };
+
+                    parse_with_prefix(token, radix, &[&unsigned[..2]]).map(Radix)
+                }
+            }
+
+            impl fmt::Display for Radix<$t> {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    fmt::Display::fmt(&self.0, f)
+                }
+            }
+        )*
+    };
+}
+
+impl_radix!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize);
+
+/// Writes an integer given by its sign and the digits of its magnitude, respecting the flags of the formatter.
+fn write_integral(
+    f: &mut fmt::Formatter<'_>,
+    nonnegative: bool,
+    prefix: &str,
+    digits: fmt::Arguments<'_>,
+) -> fmt::Result {
+    // Enough for the binary digits of the largest magnitude.
+    let mut buffer = ArrayString::<128>::new();
+    buffer.write_fmt(digits)?;
+
+    f.pad_integral(nonnegative, prefix, &buffer)
+}
+
+/// Implements [`Display`](fmt::Display) for [`Hex`], [`Bin`] and [`Oct`].
+///
+/// Negative values are written as a sign followed by the magnitude, the same way they are parsed.
+macro_rules! impl_radix_display {
+    (unsigned: $($t:ty)*) => {
+        $(impl_radix_display!(@impl $t, |value| (true, value));)*
+    };
+    (signed: $($t:ty)*) => {
+        $(impl_radix_display!(@impl $t, |value| (value >= 0, value.unsigned_abs()));)*
+    };
+    (@impl $t:ty, |$value:ident| $split:expr) => {
+        impl fmt::Display for Hex<$t> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let $value = self.0;
+                let (nonnegative, magnitude) = $split;
+                write_integral(f, nonnegative, "0x", format_args!("{magnitude:x}"))
+            }
+        }
+
+        impl fmt::Display for Bin<$t> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let $value = self.0;
+                let (nonnegative, magnitude) = $split;
+                write_integral(f, nonnegative, "0b", format_args!("{magnitude:b}"))
+            }
+        }
+
+        impl fmt::Display for Oct<$t> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let $value = self.0;
+                let (nonnegative, magnitude) = $split;
+                write_integral(f, nonnegative, "0o", format_args!("{magnitude:o}"))
+            }
+        }
+    };
+}
+
+impl_radix_display!(unsigned: u8 u16 u32 u64 u128 usize);
+impl_radix_display!(signed: i8 i16 i32 i64 i128 isize);
+
+#[cfg(test)]
+mod tests {
+    use alloc::{format, string::ToString};
+    use core::num::IntErrorKind;
+
+    use crate::{Bin, Hex, Oct, Radix};
+
+    #[test]
+    fn parses_with_and_without_prefix() {
+        assert_eq!("ff".parse(), Ok(Hex(255u8)));
+        assert_eq!("0XFF".parse(), Ok(Hex(255u8)));
+        assert_eq!("0b101".parse(), Ok(Bin(5u8)));
+        assert_eq!("101".parse(), Ok(Bin(5u8)));
+        assert_eq!("0o17".parse(), Ok(Oct(15u8)));
+        assert_eq!("17".parse(), Ok(Oct(15u8)));
+    }
+
+    #[test]
+    fn parses_signs_before_prefix() {
+        assert_eq!("-0x80".parse(), Ok(Hex(i8::MIN)));
+        assert_eq!("+0x7f".parse(), Ok(Hex(i8::MAX)));
+        assert_eq!("-1f".parse(), Ok(Hex(-31i32)));
+        assert!("0x-1".parse::<Hex<i32>>().is_err());
+        assert!("-0x1".parse::<Hex<u32>>().is_err());
+    }
+
+    #[test]
+    fn parses_negative_values_at_the_limits() {
+        assert_eq!("-0x0".parse(), Ok(Hex(0i8)));
+        assert_eq!(
+            format!("-{:#x}", i128::MIN.unsigned_abs()).parse(),
+            Ok(Hex(i128::MIN))
+        );
+
+        let kind = |token: &str| *token.parse::<Hex<i8>>().unwrap_err().kind();
+        assert_eq!(kind("-0x81"), IntErrorKind::NegOverflow);
+        assert_eq!(kind("-0x100"), IntErrorKind::NegOverflow);
+        assert_eq!(kind("-0xg"), IntErrorKind::InvalidDigit);
+        assert_eq!(kind("0x80"), IntErrorKind::PosOverflow);
+
+        let kind = |token: &str| *token.parse::<Hex<u8>>().unwrap_err().kind();
+        assert_eq!(kind("-0x0"), IntErrorKind::InvalidDigit);
+    }
+
+    #[test]
+    fn rejects_invalid_tokens() {
+        assert!("0x".parse::<Hex<u8>>().is_err());
+        assert!("0x100".parse::<Hex<u8>>().is_err());
+        assert!("2".parse::<Bin<u8>>().is_err());
+        assert!("0b".parse::<Radix<u8>>().is_err());
+        assert!("0xg".parse::<Radix<u8>>().is_err());
+    }
+
+    #[test]
+    fn detects_radix_from_prefix() {
+        assert_eq!("0x1F".parse(), Ok(Radix(31u32)));
+        assert_eq!("-0o10".parse(), Ok(Radix(-8i64)));
+        assert_eq!("0b11".parse(), Ok(Radix(3u8)));
+        assert_eq!("011".parse(), Ok(Radix(11u8)));
+        assert_eq!("-5".parse(), Ok(Radix(-5i8)));
+    }
+
+    #[test]
+    fn displays_in_radix() {
+        assert_eq!(Hex(255u8).to_string(), "ff");
+        assert_eq!(format!("{:#06}", Hex(255u16)), "0x00ff");
+        assert_eq!(Bin(5u8).to_string(), "101");
+        assert_eq!(format!("{:#}", Oct(8u8)), "0o10");
+        assert_eq!(Radix(-3i8).to_string(), "-3");
+    }
+
+    #[test]
+    fn displays_signed_values_with_sign() {
+        assert_eq!(Hex(-1i8).to_string(), "-1");
+        assert_eq!(Bin(-2i8).to_string(), "-10");
+        assert_eq!(format!("{:#}", Oct(-8i32)), "-0o10");
+        assert_eq!(format!("{:+}", Hex(31i32)), "+1f");
+        assert_eq!(format!("{:#06}", Hex(-1i16)), "-0x001");
+        assert_eq!(format!("{:>4}", Bin(-2i8)), " -10");
+    }
+
+    #[test]
+    fn parses_displayed_values() {
+        for value in [i8::MIN, -2, -1, 0, 1, i8::MAX] {
+            assert_eq!(Hex(value).to_string().parse(), Ok(Hex(value)));
+            assert_eq!(Bin(value).to_string().parse(), Ok(Bin(value)));
+            assert_eq!(Oct(value).to_string().parse(), Ok(Oct(value)));
+            assert_eq!(format!("{:#}", Hex(value)).parse(), Ok(Hex(value)));
+        }
+
+        for value in [0, 1, u128::MAX] {
+            assert_eq!(Bin(value).to_string().parse(), Ok(Bin(value)));
+            assert_eq!(format!("{:#}", Oct(value)).parse(), Ok(Oct(value)));
+        }
+    }
+}
@@ -1,26 +1,148 @@
-use std::io::{BufRead, BufReader, Lines, Read};
+use std::{
+    io::{self, BufRead, BufReader, Cursor, Read},
+    mem,
+};
 
-use crate::{FromTokens, LineCount, ReadLineError, ReadTokensError, Take};
+use crate::{
+    source::{check_utf8, parse_delimited_line},
+    FromByteTokens, FromTokens, LineCount, ReadLineError, ReadTokensError, ReaderOptions, Take,
+    TokenSource,
+};
 
+#[cfg(doc)]
+use crate::SliceReader;
 #[cfg(doc)]
 use std::io::Stdin;
 
 /// This struct wraps a [`BufReader`] to allow easy parsing of whitespace delimited files.
 #[derive(Debug)]
 pub struct TokenReader<R> {
-    lines: Lines<R>,
+    reader: R,
+    buffer: Vec<u8>,
+    pending: Vec<Vec<u8>>,
+    line_number: usize,
+    options: ReaderOptions,
 }
 
 impl<R: BufRead> TokenReader<R> {
     /// Creates a [`TokenReader`] from a type that implements [`BufRead`], such as [`Stdin`].
     pub fn new(buf_read: R) -> Self {
         TokenReader {
-            lines: buf_read.lines(),
+            reader: buf_read,
+            buffer: Vec::new(),
+            pending: Vec::new(),
+            line_number: 0,
+            options: ReaderOptions::new(),
         }
     }
 
+    /// Sets the options controlling which lines are returned.
+    ///
+    /// The options apply to all reads, including [`TokenReader::take`], [`TokenReader::peek_line`]
+    /// and everything else built on [`TokenSource`]. Lines passed to [`TokenReader::unread_line`]
+    /// are returned as they are.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use token_read::{ReaderOptions, TokenReader};
+    /// # use anyhow::Result;
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let options = ReaderOptions::new().skip_blank_lines(true);
+    /// let mut input = TokenReader::new("1 2\n\n3 4\n".as_bytes()).with_options(options);
+    ///
+    /// let values: Vec<(u8, u8)> = input.take(2).collect::<Result<_, _>>()?;
+    /// assert_eq!(values, vec![(1, 2), (3, 4)]);
+    /// #
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn with_options(mut self, options: ReaderOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Returns the number of the last line that was read, starting from one.
+    ///
+    /// Peeking at a line doesn't change the line number, while unreading a line decreases it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use token_read::TokenReader;
+    /// # use anyhow::Result;
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let mut input = TokenReader::new("first\nsecond".as_bytes());
+    /// assert_eq!(input.line_number(), 0);
+    ///
+    /// input.line_raw()?;
+    /// assert_eq!(input.line_number(), 1);
+    /// #
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn line_number(&self) -> usize {
+        self.line_number
+    }
+
+    /// Reads the next line into the buffer, stripping the line terminator.
+    ///
+    /// Lines that were peeked at or unread are returned first.
+    /// Lines skipped by the options are read over.
+    fn read_line(&mut self) -> Result<(), ReadLineError> {
+        if let Some(line) = self.pending.pop() {
+            self.buffer = line;
+            self.line_number += 1;
+            return Ok(());
+        }
+
+        loop {
+            self.read_raw_line()?;
+
+            if let Some(length) = self.options.kept_length(&self.buffer) {
+                self.buffer.truncate(length);
+                return Ok(());
+            }
+        }
+    }
+
+    /// Reads the next line of the underlying reader into the buffer, stripping the line terminator.
+    fn read_raw_line(&mut self) -> Result<(), ReadLineError> {
+        self.buffer.clear();
+
+        let read = self
+            .reader
+            .read_until(b'\n', &mut self.buffer)
+            .map_err(|source| ReadLineError::IoError { source })?;
+
+        if read == 0 {
+            return Err(ReadLineError::EndOfFile);
+        }
+
+        self.line_number += 1;
+
+        if self.buffer.ends_with(b"\n") {
+            self.buffer.pop();
+
+            if self.buffer.ends_with(b"\r") {
+                self.buffer.pop();
+            }
+        }
+
+        Ok(())
+    }
+
     /// Reads and parses a single line of whitespace delimited tokens.
     ///
+    /// A different delimiter can be set using [`ReaderOptions::delimiter`].
+    ///
     /// # Examples
     ///
     /// ```
@@ -56,10 +178,7 @@ impl<R: BufRead> TokenReader<R> {
     where
         T: FromTokens,
     {
-        let line = self.line_raw()?;
-        let tokens = line.split_whitespace();
-
-        T::from_tokens(tokens).map_err(|source| ReadTokensError::ParseError { source, line })
+        TokenSource::line(self)
     }
 
     /// Reads a single line, unmodified.
@@ -80,11 +199,60 @@ impl<R: BufRead> TokenReader<R> {
     /// # }
     /// ```
     pub fn line_raw(&mut self) -> Result<String, ReadLineError> {
-        let line = self.lines.next();
-        let line = line.ok_or_else(|| ReadLineError::EndOfFile)?;
-        let line = line.map_err(|source| ReadLineError::IoError { source })?;
+        TokenSource::line_raw(self)
+    }
+
+    /// Reads a single line as bytes, without checking whether it's valid UTF-8.
+    ///
+    /// The line terminator is not included.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use token_read::TokenReader;
+    /// # use anyhow::Result;
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let mut input = TokenReader::new(b"caf\xe9\r\nbar".as_slice());
+    /// let line = input.line_bytes()?;
+    ///
+    /// assert_eq!(line, b"caf\xe9");
+    /// #
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn line_bytes(&mut self) -> Result<&[u8], ReadLineError> {
+        self.read_line()?;
+        Ok(&self.buffer)
+    }
 
-        Ok(line)
+    /// Reads and parses a single line of whitespace delimited tokens, without requiring it to be valid UTF-8.
+    ///
+    /// The tokens are split at ASCII whitespace and parsed using [`FromByteTokens`].
+    /// This avoids UTF-8 validation, which makes it slightly faster than [`TokenReader::line`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use token_read::TokenReader;
+    /// # use anyhow::Result;
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let mut input = TokenReader::new(b"\xff 42 -7".as_slice());
+    /// let (name, a, b): (Vec<u8>, u32, i8) = input.byte_line()?;
+    ///
+    /// assert_eq!(name, b"\xff");
+    /// assert_eq!(a, 42);
+    /// assert_eq!(b, -7);
+    /// #
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn byte_line<T>(&mut self) -> Result<T, ReadTokensError<T::Error>>
+    where
+        T: FromByteTokens,
+    {
+        TokenSource::byte_line(self)
     }
 
     /// Creates an iterator that reads and parses a specific number of lines.
@@ -107,7 +275,7 @@ impl<R: BufRead> TokenReader<R> {
     /// #   Ok(())
     /// # }
     /// ```
-    pub fn take<T>(&mut self, count: usize) -> Take<'_, T, R, usize>
+    pub fn take<T>(&mut self, count: usize) -> Take<'_, T, Self, usize>
     where
         T: FromTokens,
     {
@@ -134,13 +302,169 @@ impl<R: BufRead> TokenReader<R> {
     /// #   Ok(())
     /// # }
     /// ```
-    pub fn take_count<T, S>(&mut self, count: S) -> Take<'_, T, R, S>
+    pub fn take_count<T, S>(&mut self, count: S) -> Take<'_, T, Self, S>
     where
         T: FromTokens,
         S: LineCount,
     {
         Take::new(self, count)
     }
+
+    /// Returns the next line without consuming it.
+    ///
+    /// The line will be returned again by the next read, including [`TokenReader::take`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use token_read::TokenReader;
+    /// # use anyhow::Result;
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let mut input = TokenReader::new("end\n1 2".as_bytes());
+    ///
+    /// assert_eq!(input.peek_line()?, "end");
+    /// assert_eq!(input.line_raw()?, "end");
+    /// #
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn peek_line(&mut self) -> Result<&str, ReadLineError> {
+        self.fill_pending()?;
+
+        let line = self.pending.last().map_or(&[][..], Vec::as_slice);
+        check_utf8(line, self.line_number + 1)
+    }
+
+    /// Returns the first token of the next line without consuming it, or [`None`] if the line has no tokens.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use token_read::TokenReader;
+    /// # use anyhow::Result;
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let mut input = TokenReader::new("circle 5\nsquare 2 3".as_bytes());
+    ///
+    /// if input.peek_token()? == Some("circle") {
+    ///     let (_, radius): (String, u32) = input.line()?;
+    ///     assert_eq!(radius, 5);
+    /// }
+    ///
+    /// assert_eq!(input.peek_token()?, Some("square"));
+    /// #
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn peek_token(&mut self) -> Result<Option<&str>, ReadLineError> {
+        self.fill_pending()?;
+
+        let line = self.pending.last().map_or(&[][..], Vec::as_slice);
+        let line = check_utf8(line, self.line_number + 1)?;
+
+        Ok(self.options.get_delimiter().tokens(line).next())
+    }
+
+    /// Pushes a line back, so that it will be returned by the next read.
+    ///
+    /// Lines are returned in reverse order of unreading. The line number is decreased by one,
+    /// so that it stays correct when the line that was just read is unread.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use token_read::TokenReader;
+    /// # use anyhow::Result;
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let mut input = TokenReader::new("1 2\n3 4".as_bytes());
+    ///
+    /// let line = input.line_raw()?;
+    /// input.unread_line(line);
+    ///
+    /// let values: Vec<(u8, u8)> = input.take(2).collect::<Result<_, _>>()?;
+    /// assert_eq!(values, vec![(1, 2), (3, 4)]);
+    /// #
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn unread_line(&mut self, line: String) {
+        self.pending.push(line.into_bytes());
+        self.line_number = self.line_number.saturating_sub(1);
+    }
+
+    /// Makes sure that the next line has been read into the list of pending lines.
+    fn fill_pending(&mut self) -> Result<(), ReadLineError> {
+        if self.pending.is_empty() {
+            self.read_line()?;
+            self.pending.push(mem::take(&mut self.buffer));
+            self.line_number -= 1;
+        }
+
+        Ok(())
+    }
+}
+
+impl<R: BufRead> TokenSource for TokenReader<R> {
+    /// If the whole line is already present in the buffer of the underlying reader,
+    /// it's processed in place without being copied.
+    fn with_line<T, F>(&mut self, f: F) -> Result<T, ReadLineError>
+    where
+        F: FnOnce(&[u8], usize) -> T,
+    {
+        self.with_line_and_options(|line, number, _| f(line, number))
+    }
+
+    /// Splits the line using the delimiter set in the options.
+    fn line<T>(&mut self) -> Result<T, ReadTokensError<T::Error>>
+    where
+        T: FromTokens,
+    {
+        self.with_line_and_options(|line, number, options| {
+            parse_delimited_line(line, number, options)
+        })?
+    }
+}
+
+impl<R: BufRead> TokenReader<R> {
+    /// Like [`TokenSource::with_line`], but the function also receives the options of the reader.
+    fn with_line_and_options<T, F>(&mut self, f: F) -> Result<T, ReadLineError>
+    where
+        F: FnOnce(&[u8], usize, &ReaderOptions) -> T,
+    {
+        if !self.pending.is_empty() {
+            self.read_line()?;
+            return Ok(f(&self.buffer, self.line_number, &self.options));
+        }
+
+        loop {
+            let available = self
+                .reader
+                .fill_buf()
+                .map_err(|source| ReadLineError::IoError { source })?;
+
+            let Some(end) = available.iter().position(|&b| b == b'\n') else {
+                break;
+            };
+
+            let line = &available[..end];
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+            self.line_number += 1;
+
+            if let Some(length) = self.options.kept_length(line) {
+                let result = f(&line[..length], self.line_number, &self.options);
+
+                self.reader.consume(end + 1);
+                return Ok(result);
+            }
+
+            self.reader.consume(end + 1);
+        }
+
+        self.read_line()?;
+        Ok(f(&self.buffer, self.line_number, &self.options))
+    }
 }
 
 impl<R: Read> TokenReader<BufReader<R>> {
@@ -152,6 +476,34 @@ impl<R: Read> TokenReader<BufReader<R>> {
     }
 }
 
+impl TokenReader<Cursor<Vec<u8>>> {
+    /// Creates a [`TokenReader`] by reading the whole input into memory first.
+    ///
+    /// Lines are then tokenized in place, which is often faster than reading them one by one.
+    /// If the input is already in memory, consider using a [`SliceReader`] instead.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use std::io::stdin;
+    /// # use token_read::TokenReader;
+    /// # use anyhow::Result;
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let mut input = TokenReader::slurp(stdin())?;
+    /// let (n,): (usize,) = input.line()?;
+    /// #
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn slurp<R: Read>(mut read: R) -> io::Result<Self> {
+        let mut buffer = Vec::new();
+        read.read_to_end(&mut buffer)?;
+
+        Ok(TokenReader::new(Cursor::new(buffer)))
+    }
+}
+
 impl<R> From<R> for TokenReader<R>
 where
     R: BufRead,
@@ -166,7 +518,12 @@ where
 
 #[cfg(test)]
 mod tests {
-    use crate::{ReadLineError, ReadTokensError, TokenReader};
+    use std::io::BufReader;
+
+    use crate::{
+        Delimiter, Hex, Optional, Quoted, ReadLineError, ReadTokensError, ReaderOptions,
+        RestOfLine, TokenReader, TokenSource,
+    };
 
     #[test]
     fn can_be_constructed_from_bufread() {
@@ -180,6 +537,13 @@ mod tests {
         assert_eq!(input.line_raw().unwrap(), "Hello");
     }
 
+    #[test]
+    fn can_be_constructed_by_slurping() {
+        let mut input = TokenReader::slurp("Hello\nWorld".as_bytes()).unwrap();
+        assert_eq!(input.line_raw().unwrap(), "Hello");
+        assert_eq!(input.line_raw().unwrap(), "World");
+    }
+
     #[test]
     fn can_be_constructed_with_from() {
         let mut input: TokenReader<_> = "Hello".as_bytes().into();
@@ -194,6 +558,75 @@ mod tests {
         assert!(matches!(input.line_raw(), Err(ReadLineError::EndOfFile)));
     }
 
+    #[test]
+    fn strips_line_terminators() {
+        let mut input = TokenReader::new("First\r\nSecond\r\r\nThird\r".as_bytes());
+        assert_eq!(input.line_raw().unwrap(), "First");
+        assert_eq!(input.line_raw().unwrap(), "Second\r");
+        assert_eq!(input.line_raw().unwrap(), "Third\r");
+    }
+
+    #[test]
+    fn returns_invalid_utf8() {
+        let mut input = TokenReader::new(b"ok\nab\xffc\nok".as_slice());
+        assert_eq!(input.line_raw().unwrap(), "ok");
+
+        let result = input.line::<Vec<String>>();
+        assert!(matches!(
+            result,
+            Err(ReadTokensError::InvalidUtf8 { line: 2, offset: 2 })
+        ));
+
+        assert_eq!(input.line_raw().unwrap(), "ok");
+    }
+
+    #[test]
+    fn reads_lines_longer_than_buffer() {
+        let line = "1 ".repeat(100);
+        let input = format!("{line}\n{line}\r\n{line}");
+        let mut input = TokenReader::new(BufReader::with_capacity(16, input.as_bytes()));
+
+        for _ in 0..3 {
+            let values: Vec<u8> = input.line().unwrap();
+            assert_eq!(values, vec![1; 100]);
+        }
+
+        assert!(matches!(input.line_raw(), Err(ReadLineError::EndOfFile)));
+    }
+
+    #[test]
+    fn reports_line_numbers_across_buffer_boundaries() {
+        let input: &[u8] = b"first line\nsecond line\nthird \xff line\n";
+        let mut input = TokenReader::new(BufReader::with_capacity(8, input));
+
+        assert_eq!(input.line_raw().unwrap(), "first line");
+        assert_eq!(input.line_raw().unwrap(), "second line");
+        assert!(matches!(
+            input.line_raw(),
+            Err(ReadLineError::InvalidUtf8 { line: 3, offset: 6 })
+        ));
+    }
+
+    #[test]
+    fn reads_byte_lines() {
+        let mut input = TokenReader::new(b"\xfe\xff\n".as_slice());
+        assert_eq!(input.line_bytes().unwrap(), b"\xfe\xff");
+        assert!(matches!(input.line_bytes(), Err(ReadLineError::EndOfFile)));
+    }
+
+    #[test]
+    fn parses_byte_lines() {
+        let mut input = TokenReader::new(b" 12\t\xc0 -3 \nx".as_slice());
+        let (a, b, c): (u8, Vec<u8>, i64) = input.byte_line().unwrap();
+
+        assert_eq!(a, 12);
+        assert_eq!(b, b"\xc0");
+        assert_eq!(c, -3);
+
+        let result = input.byte_line::<(u8,)>();
+        assert!(matches!(result, Err(ReadTokensError::ParseError { line, .. }) if line == "x"));
+    }
+
     #[test]
     fn reads_single_value() {
         let mut input = TokenReader::new("13".as_bytes());
@@ -263,6 +696,152 @@ mod tests {
         }
     }
 
+    #[test]
+    fn peeks_without_consuming() {
+        let mut input = TokenReader::new("1 2\n\n3".as_bytes());
+
+        assert_eq!(input.peek_line().unwrap(), "1 2");
+        assert_eq!(input.peek_token().unwrap(), Some("1"));
+        assert_eq!(input.line_number(), 0);
+
+        let values: Vec<Vec<u8>> = input.take(2).collect::<Result<_, _>>().unwrap();
+        assert_eq!(values, vec![vec![1, 2], vec![]]);
+
+        assert_eq!(input.peek_token().unwrap(), Some("3"));
+        assert_eq!(input.line_bytes().unwrap(), b"3");
+        assert_eq!(input.line_number(), 3);
+        assert!(matches!(input.peek_line(), Err(ReadLineError::EndOfFile)));
+    }
+
+    #[test]
+    fn unreads_lines_in_reverse_order() {
+        let mut input = TokenReader::new("a\nb\nc".as_bytes());
+
+        let a = input.line_raw().unwrap();
+        let b = input.line_raw().unwrap();
+        input.unread_line(b);
+        input.unread_line(a);
+        assert_eq!(input.line_number(), 0);
+
+        let lines: Vec<(char,)> = input.take(3).collect::<Result<_, _>>().unwrap();
+        assert_eq!(lines, vec![('a',), ('b',), ('c',)]);
+        assert_eq!(input.line_number(), 3);
+    }
+
+    #[test]
+    fn keeps_invalid_line_when_peeking() {
+        let mut input = TokenReader::new(b"ok\n\xff".as_slice());
+        input.line_raw().unwrap();
+
+        assert!(matches!(
+            input.peek_line(),
+            Err(ReadLineError::InvalidUtf8 { line: 2, offset: 0 })
+        ));
+        assert_eq!(input.line_bytes().unwrap(), b"\xff");
+    }
+
+    #[test]
+    fn skips_lines_according_to_options() {
+        let options = ReaderOptions::new()
+            .skip_blank_lines(true)
+            .comment_prefix("#")
+            .trailing_comment("//");
+        let text = "# header\n\n1 2 // first\n  # note\n3 4\n\n";
+
+        for mut input in [
+            TokenReader::new(BufReader::new(text.as_bytes())),
+            TokenReader::new(BufReader::with_capacity(4, text.as_bytes())),
+        ] {
+            input = input.with_options(options.clone());
+
+            assert_eq!(input.peek_line().unwrap(), "1 2");
+            let values: Vec<(u8, u8)> = input.take(2).collect::<Result<_, _>>().unwrap();
+
+            assert_eq!(values, vec![(1, 2), (3, 4)]);
+            assert_eq!(input.line_number(), 5);
+            assert!(matches!(input.line_raw(), Err(ReadLineError::EndOfFile)));
+        }
+    }
+
+    #[test]
+    fn reports_real_line_numbers_with_skipped_lines() {
+        let options = ReaderOptions::new().skip_blank_lines(true);
+        let mut input = TokenReader::new(b"\n\n\xff".as_slice()).with_options(options);
+
+        assert!(matches!(
+            input.line_raw(),
+            Err(ReadLineError::InvalidUtf8 { line: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn returns_unread_lines_unfiltered() {
+        let options = ReaderOptions::new().comment_prefix("#");
+        let mut input = TokenReader::new("1".as_bytes()).with_options(options);
+
+        input.unread_line("# 2".to_owned());
+
+        assert_eq!(input.line_raw().unwrap(), "# 2");
+        assert_eq!(input.line_raw().unwrap(), "1");
+    }
+
+    #[test]
+    fn splits_lines_using_delimiter() {
+        let options = ReaderOptions::new().delimiter(Delimiter::chars(";"));
+        let mut input = TokenReader::new("a b;1\nc;2\n".as_bytes()).with_options(options);
+
+        assert_eq!(input.peek_token().unwrap(), Some("a b"));
+
+        let values: Vec<(String, u8)> = input.take(2).collect::<Result<_, _>>().unwrap();
+        assert_eq!(values, vec![("a b".to_owned(), 1), ("c".to_owned(), 2)]);
+    }
+
+    #[test]
+    fn reads_quoted_tokens() {
+        let options = ReaderOptions::new().delimiter(Delimiter::quoted_whitespace());
+        let mut input = TokenReader::new("\"New York\" 8468000".as_bytes()).with_options(options);
+
+        let (Quoted(city), population): (Quoted<String>, u64) = input.line().unwrap();
+        assert_eq!((city.as_str(), population), ("New York", 8468000));
+    }
+
+    #[test]
+    fn uses_delimiter_through_references() {
+        fn read(mut input: impl TokenSource) -> Vec<u8> {
+            input.line().unwrap()
+        }
+
+        let options = ReaderOptions::new().delimiter(Delimiter::string(", "));
+        let mut input = TokenReader::new("1, 2, 3".as_bytes()).with_options(options);
+
+        assert_eq!(read(&mut input), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn reads_integers_in_radix() {
+        let options = ReaderOptions::new()
+            .delimiter(Delimiter::chars(","))
+            .integer_radix(16);
+        let input = "cafe,ff,-1f,a,0x10,x y\n1a,Ff\n10\n1 2\n".as_bytes();
+        let mut input = TokenReader::new(input).with_options(options);
+
+        let (word, a, b, c, Hex(d), rest): (String, u8, i32, char, Hex<u8>, RestOfLine) =
+            input.line().unwrap();
+        assert_eq!((word.as_str(), a, b, c, d), ("cafe", 255, -31, 'a', 16));
+        assert_eq!(rest.as_str(), "x y");
+
+        let values: Vec<u8> = input.line().unwrap();
+        assert_eq!(values, [26, 255]);
+
+        let lines: Vec<(u32, Optional<u32>)> = input.take(1).collect::<Result<_, _>>().unwrap();
+        assert!(matches!(lines[..], [(16, Optional(None))]));
+
+        assert!(matches!(
+            input.line::<[u8; 1]>(),
+            Err(ReadTokensError::ParseError { line, .. }) if line == "1 2"
+        ));
+    }
+
     #[test]
     fn take_count_gets_multiple_lines() {
         let mut input = TokenReader::new("0\n1\n2\nx".as_bytes());
@@ -0,0 +1,122 @@
+use alloc::string::String;
+use core::{fmt, ops::Deref};
+
+#[cfg(doc)]
+use crate::{FromTokens, Remainder};
+
+/// The rest of a line, with its original spacing.
+///
+/// It can be used as the last element of a tuple to read free text at the end of a line.
+/// Leading and trailing whitespace is removed and the rest may be empty.
+///
+/// The exact text is only available when the tokens come from a [`Remainder`],
+/// which is the case when reading lines and when using [`FromTokens::from_line_tokens`].
+/// Otherwise, the remaining tokens are joined by single spaces.
+///
+/// # Example
+///
+/// ```
+/// # use token_read::{RestOfLine, SliceReader};
+/// # use anyhow::Result;
+/// #
+/// # fn main() -> Result<()> {
+/// let mut input = SliceReader::new("8468000 New  York\n42");
+///
+/// let (population, city): (u64, RestOfLine) = input.line()?;
+/// let (answer, comment): (u8, RestOfLine) = input.line()?;
+///
+/// assert_eq!(population, 8468000);
+/// assert_eq!(city.as_str(), "New  York");
+/// assert_eq!(comment.as_str(), "");
+/// #
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RestOfLine(pub String);
+
+impl RestOfLine {
+    /// Returns the text as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the text as an owned string.
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+
+    /// Creates the text by joining the remaining tokens by single spaces.
+    pub(crate) fn from_remaining<'a, I>(tokens: I) -> Self
+    where
+        I: Iterator<Item = &'a str>,
+    {
+        let mut text = String::new();
+
+        for token in tokens {
+            if !text.is_empty() {
+                text.push(' ');
+            }
+
+            text.push_str(token);
+        }
+
+        RestOfLine(text)
+    }
+}
+
+impl Deref for RestOfLine {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for RestOfLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<RestOfLine> for String {
+    fn from(value: RestOfLine) -> Self {
+        value.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+
+    use crate::{FromTokens, RestOfLine, ToTokens};
+
+    #[test]
+    fn keeps_original_spacing() {
+        let (a, rest): (u8, RestOfLine) = "1  a \t b  ".parse_tokens().unwrap();
+
+        assert_eq!(a, 1);
+        assert_eq!(rest.as_str(), "a \t b");
+    }
+
+    #[test]
+    fn reads_whole_line() {
+        let (rest,): (RestOfLine,) = " a  b ".parse_tokens().unwrap();
+        assert_eq!(rest.as_str(), "a  b");
+    }
+
+    #[test]
+    fn joins_tokens_without_remainder() {
+        let tokens = ["1", "a", "b"].into_iter();
+        let (a, rest) = <(u8, RestOfLine)>::from_tokens(tokens).unwrap();
+
+        assert_eq!(a, 1);
+        assert_eq!(String::from(rest), "a b");
+    }
+
+    #[test]
+    fn reports_missing_tokens_before_rest() {
+        let result = "".parse_tokens::<(u8, RestOfLine)>();
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,741 @@
+use alloc::string::{String, ToString};
+use core::fmt::{self, Display, Write};
+
+use serde::{
+    ser::{
+        self, Impossible, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant,
+        SerializeTuple, SerializeTupleStruct, SerializeTupleVariant,
+    },
+    Serialize,
+};
+use thiserror::Error;
+
+use crate::length_prefixed;
+
+#[cfg(doc)]
+use crate::Deserializer;
+
+/// An error returned when serializing a value into whitespace delimited tokens.
+#[derive(Error, Debug)]
+pub enum SerializeError {
+    #[error("token \"{token}\" is empty or contains whitespace")]
+    InvalidToken { token: String },
+    #[error("sequences inside other sequences must be length-prefixed")]
+    NestedSequence,
+    #[error("length-prefixed sequences must have a known length")]
+    UnknownLength,
+    #[error("serializing {kind} is not supported")]
+    Unsupported { kind: &'static str },
+    #[error("failed to write output")]
+    WriteError { source: fmt::Error },
+    #[error("{message}")]
+    Custom { message: String },
+}
+
+impl From<fmt::Error> for SerializeError {
+    fn from(source: fmt::Error) -> Self {
+        SerializeError::WriteError { source }
+    }
+}
+
+impl ser::Error for SerializeError {
+    fn custom<T: Display>(message: T) -> Self {
+        SerializeError::Custom {
+            message: message.to_string(),
+        }
+    }
+}
+
+/// A [serde](https://serde.rs) serializer that writes values as whitespace delimited tokens.
+///
+/// It's the counterpart of [`Deserializer`], so values written by it can be read back:
+///
+/// * Primitives and strings are written as a single token.
+///   Strings must not be empty or contain whitespace.
+/// * Structs, tuples and tuple structs are written as consecutive tokens separated by spaces.
+/// * Sequences and maps are written on their own line.
+///   Sequences marked with [`length_prefixed`] are instead written as their length followed by the elements.
+/// * Enums are written as the name of the variant, followed by its fields.
+/// * Options are written as their value if present, or not at all.
+///   This means that [`None`] can only be read back if it's the last value on a line
+///   and doesn't directly follow a sequence.
+///
+/// It's usually used through [`to_string`].
+#[derive(Debug)]
+pub struct Serializer<W> {
+    output: W,
+    line_empty: bool,
+    in_sequence: bool,
+}
+
+impl<W: Write> Serializer<W> {
+    /// Creates a [`Serializer`] writing to a [`Write`] implementation, such as [`String`].
+    pub fn new(output: W) -> Self {
+        Serializer {
+            output,
+            line_empty: true,
+            in_sequence: false,
+        }
+    }
+
+    /// Ends the current line if it isn't empty.
+    pub fn end_line(&mut self) -> Result<(), SerializeError> {
+        if !self.line_empty {
+            self.output.write_char('\n')?;
+            self.line_empty = true;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.output
+    }
+
+    /// Writes a single token, separating it from the previous one.
+    fn token<T: Display>(&mut self, token: T) -> Result<(), SerializeError> {
+        if !self.line_empty {
+            self.output.write_char(' ')?;
+        }
+
+        write!(self.output, "{token}")?;
+        self.line_empty = false;
+
+        Ok(())
+    }
+
+    /// Checks that a string can be written as a single token and writes it.
+    fn checked_token(&mut self, token: &str) -> Result<(), SerializeError> {
+        if token.is_empty() || token.contains(char::is_whitespace) {
+            return Err(SerializeError::InvalidToken {
+                token: token.to_string(),
+            });
+        }
+
+        self.token(token)
+    }
+
+    /// Starts a sequence or a map written on its own line.
+    fn start_line(&mut self) -> Result<Compound<'_, W>, SerializeError> {
+        if self.in_sequence {
+            return Err(SerializeError::NestedSequence);
+        }
+
+        self.end_line()?;
+        self.in_sequence = true;
+
+        Ok(Compound {
+            ser: self,
+            line: true,
+        })
+    }
+}
+
+macro_rules! serialize_display {
+    ($($method:ident($ty:ty)),* $(,)?) => {
+        $(
+            fn $method(self, value: $ty) -> Result<Self::Ok, Self::Error> {
+                self.token(value)
+            }
+        )*
+    };
+}
+
+impl<'a, W: Write> ser::Serializer for &'a mut Serializer<W> {
+    type Ok = ();
+    type Error = SerializeError;
+
+    type SerializeSeq = Compound<'a, W>;
+    type SerializeTuple = Compound<'a, W>;
+    type SerializeTupleStruct = Compound<'a, W>;
+    type SerializeTupleVariant = Compound<'a, W>;
+    type SerializeMap = Compound<'a, W>;
+    type SerializeStruct = Compound<'a, W>;
+    type SerializeStructVariant = Compound<'a, W>;
+
+    serialize_display! {
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_i128(i128),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_u128(u128),
+        serialize_f32(f32),
+        serialize_f64(f64),
+    }
+
+    fn serialize_char(self, value: char) -> Result<Self::Ok, Self::Error> {
+        self.checked_token(value.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_str(self, value: &str) -> Result<Self::Ok, Self::Error> {
+        self.checked_token(value)
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<Self::Ok, Self::Error> {
+        match core::str::from_utf8(value) {
+            Ok(value) => self.checked_token(value),
+            Err(_) => Err(SerializeError::InvalidToken {
+                token: String::from_utf8_lossy(value).into_owned(),
+            }),
+        }
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.checked_token(variant)
+    }
+
+    fn serialize_newtype_struct<T>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        if name == length_prefixed::NAME {
+            value.serialize(LengthPrefixed { ser: self })
+        } else {
+            value.serialize(self)
+        }
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.checked_token(variant)?;
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, _length: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        self.start_line()
+    }
+
+    fn serialize_tuple(self, _length: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Ok(Compound {
+            ser: self,
+            line: false,
+        })
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _length: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Ok(Compound {
+            ser: self,
+            line: false,
+        })
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _length: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        self.checked_token(variant)?;
+
+        Ok(Compound {
+            ser: self,
+            line: false,
+        })
+    }
+
+    fn serialize_map(self, _length: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        self.start_line()
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _length: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Ok(Compound {
+            ser: self,
+            line: false,
+        })
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _length: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        self.checked_token(variant)?;
+
+        Ok(Compound {
+            ser: self,
+            line: false,
+        })
+    }
+
+    fn is_human_readable(&self) -> bool {
+        true
+    }
+}
+
+/// Serializes a sequence prefixed by its length, see [`length_prefixed`].
+///
+/// Only sequences, possibly wrapped in newtype structs, can be prefixed by their length.
+struct LengthPrefixed<'a, W> {
+    ser: &'a mut Serializer<W>,
+}
+
+macro_rules! serialize_unsupported {
+    ($($method:ident($($arg:ident: $ty:ty),*) -> $result:ty),* $(,)?) => {
+        $(
+            fn $method(self, $($arg: $ty),*) -> Result<$result, Self::Error> {
+                Err(unsupported_length_prefixed())
+            }
+        )*
+    };
+}
+
+impl<'a, W: Write> ser::Serializer for LengthPrefixed<'a, W> {
+    type Ok = ();
+    type Error = SerializeError;
+
+    type SerializeSeq = Compound<'a, W>;
+    type SerializeTuple = Impossible<(), SerializeError>;
+    type SerializeTupleStruct = Impossible<(), SerializeError>;
+    type SerializeTupleVariant = Impossible<(), SerializeError>;
+    type SerializeMap = Impossible<(), SerializeError>;
+    type SerializeStruct = Impossible<(), SerializeError>;
+    type SerializeStructVariant = Impossible<(), SerializeError>;
+
+    serialize_unsupported! {
+        serialize_bool(_value: bool) -> (),
+        serialize_i8(_value: i8) -> (),
+        serialize_i16(_value: i16) -> (),
+        serialize_i32(_value: i32) -> (),
+        serialize_i64(_value: i64) -> (),
+        serialize_i128(_value: i128) -> (),
+        serialize_u8(_value: u8) -> (),
+        serialize_u16(_value: u16) -> (),
+        serialize_u32(_value: u32) -> (),
+        serialize_u64(_value: u64) -> (),
+        serialize_u128(_value: u128) -> (),
+        serialize_f32(_value: f32) -> (),
+        serialize_f64(_value: f64) -> (),
+        serialize_char(_value: char) -> (),
+        serialize_str(_value: &str) -> (),
+        serialize_bytes(_value: &[u8]) -> (),
+        serialize_none() -> (),
+        serialize_unit() -> (),
+        serialize_unit_struct(_name: &'static str) -> (),
+        serialize_unit_variant(_name: &'static str, _index: u32, _variant: &'static str) -> (),
+        serialize_tuple(_length: usize) -> Self::SerializeTuple,
+        serialize_tuple_struct(_name: &'static str, _length: usize) -> Self::SerializeTupleStruct,
+        serialize_tuple_variant(
+            _name: &'static str,
+            _index: u32,
+            _variant: &'static str,
+            _length: usize
+        ) -> Self::SerializeTupleVariant,
+        serialize_map(_length: Option<usize>) -> Self::SerializeMap,
+        serialize_struct(_name: &'static str, _length: usize) -> Self::SerializeStruct,
+        serialize_struct_variant(
+            _name: &'static str,
+            _index: u32,
+            _variant: &'static str,
+            _length: usize
+        ) -> Self::SerializeStructVariant,
+    }
+
+    fn serialize_some<T>(self, _value: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        Err(unsupported_length_prefixed())
+    }
+
+    fn serialize_newtype_struct<T>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        Err(unsupported_length_prefixed())
+    }
+
+    fn serialize_seq(self, length: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        let length = length.ok_or(SerializeError::UnknownLength)?;
+        self.ser.token(length)?;
+
+        Ok(Compound {
+            ser: self.ser,
+            line: false,
+        })
+    }
+
+    fn is_human_readable(&self) -> bool {
+        true
+    }
+}
+
+/// Returns the error for length-prefixed values that aren't sequences.
+fn unsupported_length_prefixed() -> SerializeError {
+    SerializeError::Unsupported {
+        kind: "length-prefixed values other than sequences",
+    }
+}
+
+/// Serializes the elements of a compound value, ending the line afterwards if it's a sequence or a map.
+#[derive(Debug)]
+pub struct Compound<'a, W> {
+    ser: &'a mut Serializer<W>,
+    line: bool,
+}
+
+impl<W: Write> Compound<'_, W> {
+    fn element<T>(&mut self, value: &T) -> Result<(), SerializeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(&mut *self.ser)
+    }
+
+    fn finish(self) -> Result<(), SerializeError> {
+        if self.line {
+            self.ser.in_sequence = false;
+            self.ser.output.write_char('\n')?;
+            self.ser.line_empty = true;
+        }
+
+        Ok(())
+    }
+}
+
+macro_rules! impl_compound {
+    ($($trait:ident::$method:ident),* $(,)?) => {
+        $(
+            impl<W: Write> $trait for Compound<'_, W> {
+                type Ok = ();
+                type Error = SerializeError;
+
+                fn $method<T>(&mut self, value: &T) -> Result<(), Self::Error>
+                where
+                    T: Serialize + ?Sized,
+                {
+                    self.element(value)
+                }
+
+                fn end(self) -> Result<(), Self::Error> {
+                    self.finish()
+                }
+            }
+        )*
+    };
+}
+
+impl_compound! {
+    SerializeSeq::serialize_element,
+    SerializeTuple::serialize_element,
+    SerializeTupleStruct::serialize_field,
+    SerializeTupleVariant::serialize_field,
+}
+
+impl<W: Write> SerializeMap for Compound<'_, W> {
+    type Ok = ();
+    type Error = SerializeError;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.element(key)
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        self.finish()
+    }
+}
+
+impl<W: Write> SerializeStruct for Compound<'_, W> {
+    type Ok = ();
+    type Error = SerializeError;
+
+    fn serialize_field<T>(&mut self, _key: &'static str, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        self.finish()
+    }
+}
+
+impl<W: Write> SerializeStructVariant for Compound<'_, W> {
+    type Ok = ();
+    type Error = SerializeError;
+
+    fn serialize_field<T>(&mut self, _key: &'static str, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        self.finish()
+    }
+}
+
+/// Serializes a value into a string of whitespace delimited tokens.
+///
+/// The output ends with a newline, unless it's empty.
+/// See [`Serializer`] for details on how values are mapped to tokens.
+///
+/// # Example
+///
+/// ```
+/// # use serde::Serialize;
+/// # use anyhow::Result;
+/// #
+/// #[derive(Serialize)]
+/// struct Test {
+///     name: String,
+///     values: Vec<u32>,
+/// }
+///
+/// # fn main() -> Result<()> {
+/// let test = Test { name: "first".to_owned(), values: vec![1, 2, 3] };
+///
+/// assert_eq!(token_read::to_string(&test)?, "first\n1 2 3\n");
+/// #
+/// #   Ok(())
+/// # }
+/// ```
+pub fn to_string<T>(value: &T) -> Result<String, SerializeError>
+where
+    T: Serialize + ?Sized,
+{
+    let mut ser = Serializer::new(String::new());
+
+    value.serialize(&mut ser)?;
+    ser.end_line()?;
+
+    Ok(ser.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, fmt::Debug};
+
+    use proptest::prelude::*;
+    use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+    use crate::{from_str, length_prefixed, to_string, SerializeError};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Polygon {
+        name: String,
+        #[serde(with = "length_prefixed")]
+        points: Vec<Point>,
+        weight: Option<f64>,
+        tags: Vec<String>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    enum Query {
+        #[serde(rename = "add")]
+        Add { at: usize, value: i64 },
+        #[serde(rename = "move")]
+        Move(Point, Point),
+        #[serde(rename = "sum")]
+        Sum,
+    }
+
+    fn round_trip<T>(value: &T) -> T
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        from_str(&to_string(value).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn writes_tokens_and_lines() {
+        let polygon = Polygon {
+            name: "square".to_owned(),
+            points: vec![Point { x: 0, y: 0 }, Point { x: 1, y: 1 }],
+            weight: Some(0.5),
+            tags: vec!["a".to_owned(), "b".to_owned()],
+        };
+
+        assert_eq!(to_string(&polygon).unwrap(), "square 2 0 0 1 1 0.5\na b\n");
+        assert_eq!(round_trip(&polygon), polygon);
+    }
+
+    #[test]
+    fn round_trips_empty_sequences() {
+        let value: (u8, Vec<u8>, Vec<u8>, u8) = (1, vec![], vec![2], 3);
+
+        assert_eq!(to_string(&value).unwrap(), "1\n\n2\n3\n");
+        assert_eq!(round_trip(&value), value);
+    }
+
+    #[test]
+    fn round_trips_enums_and_maps() {
+        let queries = vec![
+            Query::Add { at: 3, value: -7 },
+            Query::Move(Point { x: 1, y: 2 }, Point { x: 3, y: 4 }),
+            Query::Sum,
+        ];
+        assert_eq!(to_string(&queries).unwrap(), "add 3 -7 move 1 2 3 4 sum\n");
+        assert_eq!(round_trip(&queries), queries);
+
+        let map = BTreeMap::from([("x".to_owned(), 1u8), ("y".to_owned(), 2)]);
+        assert_eq!(round_trip(&map), map);
+    }
+
+    #[test]
+    fn returns_error_on_invalid_tokens() {
+        assert!(matches!(
+            to_string("two words"),
+            Err(SerializeError::InvalidToken { token }) if token == "two words"
+        ));
+        assert!(matches!(
+            to_string(""),
+            Err(SerializeError::InvalidToken { .. })
+        ));
+    }
+
+    #[test]
+    fn returns_error_on_nested_sequences() {
+        let value = vec![vec![1u8]];
+        assert!(matches!(
+            to_string(&value),
+            Err(SerializeError::NestedSequence)
+        ));
+    }
+
+    #[test]
+    fn rejects_length_prefixed_non_sequences() {
+        #[derive(Serialize)]
+        struct Test {
+            #[serde(serialize_with = "length_prefixed::serialize")]
+            pair: (u8, u8),
+            values: Vec<u8>,
+        }
+
+        let value = Test {
+            pair: (1, 2),
+            values: vec![3, 4],
+        };
+
+        assert!(matches!(
+            to_string(&value),
+            Err(SerializeError::Unsupported { .. })
+        ));
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Grid {
+        #[serde(with = "length_prefixed")]
+        rows: Vec<Vec<u8>>,
+    }
+
+    fn assert_round_trip<T>(value: T) -> Result<(), TestCaseError>
+    where
+        T: Serialize + DeserializeOwned + Debug + PartialEq,
+    {
+        let text = to_string(&value).unwrap();
+        prop_assert_eq!(
+            from_str::<T>(&text).unwrap(),
+            value,
+            "serialized as {:?}",
+            text
+        );
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn round_trips_values(
+            a in any::<i64>(),
+            b in any::<f64>().prop_filter("NaN isn't equal to itself", |f| !f.is_nan()),
+            c in "[^\\s]{1,8}",
+            d in prop::collection::vec(any::<u32>(), 0..8),
+            e in any::<Option<char>>().prop_filter("must be a token", |c| !c.is_some_and(char::is_whitespace)),
+        ) {
+            assert_round_trip((a, b, c, e, d))?;
+        }
+
+        #[test]
+        fn round_trips_grids(rows in prop::collection::vec(prop::collection::vec(any::<u8>(), 0..4), 0..4)) {
+            assert_round_trip(Grid { rows })?;
+        }
+    }
+}
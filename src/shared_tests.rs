@@ -0,0 +1,144 @@
+//! Tests checking that all readers behave the same way.
+
+macro_rules! reader_tests {
+    ($name:ident, $create:expr) => {
+        mod $name {
+            #[allow(unused_imports)]
+            use std::io::BufReader;
+
+            #[allow(unused_imports)]
+            use crate::{
+                graph::EdgeListOptions,
+                ParseTokenPatternError, ReadLineError, ReadTokensError,
+                SliceReader, TokenReader, TokenSource,
+            };
+
+            fn create(input: &'static [u8]) -> impl TokenSource {
+                #[allow(clippy::redundant_closure_call)]
+                ($create)(input)
+            }
+
+            fn line_bytes(input: &mut impl TokenSource) -> Result<Vec<u8>, ReadLineError> {
+                input.with_line(|line, _| line.to_vec())
+            }
+
+            #[test]
+            fn reads_raw_lines() {
+                let mut input = create(b"First\nSecond\r\n\nLast\r");
+                assert_eq!(input.line_raw().unwrap(), "First");
+                assert_eq!(input.line_raw().unwrap(), "Second");
+                assert_eq!(input.line_raw().unwrap(), "");
+                assert_eq!(input.line_raw().unwrap(), "Last\r");
+                assert!(matches!(input.line_raw(), Err(ReadLineError::EndOfFile)));
+            }
+
+            #[test]
+            fn reads_nothing_from_empty_input() {
+                let mut input = create(b"");
+                assert!(matches!(input.line_raw(), Err(ReadLineError::EndOfFile)));
+            }
+
+            #[test]
+            fn stops_after_final_newline() {
+                let mut input = create(b"5\n");
+                assert_eq!(input.line::<(u8,)>().unwrap(), (5,));
+                assert!(matches!(input.line::<(u8,)>(), Err(ReadTokensError::EndOfFile)));
+            }
+
+            #[test]
+            fn reads_tokens() {
+                let mut input = create(b" 1\t-2   3 \nx y");
+                let values: Vec<i32> = input.line().unwrap();
+                let (x, y): (char, String) = input.line().unwrap();
+
+                assert_eq!(values, vec![1, -2, 3]);
+                assert_eq!((x, y.as_str()), ('x', "y"));
+            }
+
+            #[test]
+            fn reads_byte_tokens() {
+                let mut input = create(b"\xff 7\n");
+                let (a, b): (Vec<u8>, u8) = input.byte_line().unwrap();
+                assert_eq!((a, b), (vec![0xff], 7));
+            }
+
+            #[test]
+            fn reads_bytes() {
+                let mut input = create(b"\xfe\xff\r\nok");
+                assert_eq!(line_bytes(&mut input).unwrap(), b"\xfe\xff");
+                assert_eq!(line_bytes(&mut input).unwrap(), b"ok");
+            }
+
+            #[test]
+            fn takes_lines() {
+                let mut input = create(b"1 a\n2 b\n3 c\n4 d");
+                let lines: Vec<(u8, char)> = input.take(3).collect::<Result<_, _>>().unwrap();
+
+                assert_eq!(lines, vec![(1, 'a'), (2, 'b'), (3, 'c')]);
+                assert_eq!(input.line_raw().unwrap(), "4 d");
+            }
+
+            #[test]
+            fn returns_parse_error_with_line() {
+                let mut input = create(b"1 2 3\n");
+                let result = input.line::<(u8, u8)>();
+
+                assert!(matches!(
+                    result,
+                    Err(ReadTokensError::ParseError {
+                        source: ParseTokenPatternError::TooManyTokens { expected: 2 },
+                        line,
+                    }) if line == "1 2 3"
+                ));
+            }
+
+            #[test]
+            fn returns_invalid_utf8_with_position() {
+                let mut input = create(b"a\nb\nxy\xc3(\nc");
+                input.line_raw().unwrap();
+                input.line_raw().unwrap();
+
+                assert!(matches!(
+                    input.line_raw(),
+                    Err(ReadLineError::InvalidUtf8 { line: 3, offset: 2 })
+                ));
+                assert_eq!(input.line_raw().unwrap(), "c");
+            }
+
+            #[test]
+            fn reads_graphs() {
+                let mut input = create(b"3 2\n1 2\n3 2\nend");
+                let graph = (&mut input)
+                    .graph::<(usize, usize)>(EdgeListOptions::new())
+                    .unwrap();
+
+                assert_eq!(graph.neighbours(), vec![vec![1], vec![0, 2], vec![1]]);
+                assert_eq!(input.line_raw().unwrap(), "end");
+            }
+
+            #[test]
+            fn reads_through_mutable_references() {
+                fn sum(mut input: impl TokenSource) -> u32 {
+                    let (count,): (u8,) = input.line().unwrap();
+                    input
+                        .take_count::<(u32,), _>(count)
+                        .map(|line| line.unwrap().0)
+                        .sum()
+                }
+
+                let mut input = create(b"2\n3\n4\nend");
+
+                assert_eq!(sum(&mut input), 7);
+                assert_eq!(input.line_raw().unwrap(), "end");
+            }
+        }
+    };
+}
+
+reader_tests!(token_reader, TokenReader::new);
+reader_tests!(token_reader_with_small_buffer, |input| TokenReader::new(
+    BufReader::with_capacity(4, input)
+));
+reader_tests!(token_reader_slurped, |input| TokenReader::slurp(input)
+    .unwrap());
+reader_tests!(slice_reader, SliceReader::from_bytes);
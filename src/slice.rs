@@ -0,0 +1,396 @@
+use alloc::vec::Vec;
+
+use crate::{
+    source::{check_utf8, parse_delimited_line},
+    FromByteTokens, FromTokens, LineCount, ReadLineError, ReadTokensError, ReaderOptions, Take,
+    TokenSource, Tokens,
+};
+
+#[cfg(all(doc, feature = "std"))]
+use crate::TokenReader;
+
+/// A reader that parses whitespace delimited input which is already in memory.
+///
+#[cfg_attr(
+    feature = "std",
+    doc = "It provides the same methods as [`TokenReader`], but returns lines and tokens"
+)]
+#[cfg_attr(
+    not(feature = "std"),
+    doc = "It provides the same methods as `TokenReader`, but returns lines and tokens"
+)]
+/// borrowed from the input instead of allocating them.
+///
+/// # Example
+///
+/// ```
+/// # use token_read::SliceReader;
+/// # use anyhow::Result;
+/// #
+/// # fn main() -> Result<()> {
+/// let mut input = SliceReader::new("2\nAlice Bob\nCharlie Dave");
+///
+/// let (count,): (usize,) = input.line()?;
+/// let pairs: Vec<(String, String)> = input.take(count).collect::<Result<_, _>>()?;
+///
+/// assert_eq!(pairs[1], ("Charlie".to_owned(), "Dave".to_owned()));
+/// #
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SliceReader<'a> {
+    rest: &'a [u8],
+    pending: Vec<&'a [u8]>,
+    line_number: usize,
+    options: ReaderOptions,
+}
+
+impl<'a> SliceReader<'a> {
+    /// Creates a [`SliceReader`] from a string.
+    pub fn new(input: &'a str) -> Self {
+        SliceReader::from_bytes(input.as_bytes())
+    }
+
+    /// Creates a [`SliceReader`] from bytes, which don't have to be valid UTF-8.
+    ///
+    /// Lines are checked to be valid UTF-8 when they are read as strings.
+    pub fn from_bytes(input: &'a [u8]) -> Self {
+        SliceReader {
+            rest: input,
+            pending: Vec::new(),
+            line_number: 0,
+            options: ReaderOptions::new(),
+        }
+    }
+
+    /// Sets the options controlling which lines are returned.
+    ///
+    /// The options apply to all reads, including [`SliceReader::take`], [`SliceReader::peek_line`]
+    /// and everything else built on [`TokenSource`]. Lines passed to [`SliceReader::unread_line`]
+    /// are returned as they are.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use token_read::{ReaderOptions, SliceReader};
+    /// # use anyhow::Result;
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let options = ReaderOptions::new().comment_prefix("#");
+    /// let mut input = SliceReader::new("# count\n3").with_options(options);
+    ///
+    /// assert_eq!(input.line_raw()?, "3");
+    /// assert_eq!(input.line_number(), 2);
+    /// #
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn with_options(mut self, options: ReaderOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Returns the number of the last line that was read, starting from one.
+    ///
+    /// Peeking at a line doesn't change the line number, while unreading a line decreases it.
+    pub fn line_number(&self) -> usize {
+        self.line_number
+    }
+
+    /// Reads a single line as bytes, stripping the line terminator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use token_read::SliceReader;
+    /// # use anyhow::Result;
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let mut input = SliceReader::from_bytes(b"caf\xe9\r\nbar");
+    /// let line = input.line_bytes()?;
+    ///
+    /// assert_eq!(line, b"caf\xe9");
+    /// #
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn line_bytes(&mut self) -> Result<&'a [u8], ReadLineError> {
+        if let Some(line) = self.pending.pop() {
+            self.line_number += 1;
+            return Ok(line);
+        }
+
+        loop {
+            let line = self.raw_line()?;
+
+            if let Some(length) = self.options.kept_length(line) {
+                return Ok(&line[..length]);
+            }
+        }
+    }
+
+    /// Reads the next line of the input, stripping the line terminator.
+    fn raw_line(&mut self) -> Result<&'a [u8], ReadLineError> {
+        if self.rest.is_empty() {
+            return Err(ReadLineError::EndOfFile);
+        }
+
+        let (line, rest) = match self.rest.iter().position(|&b| b == b'\n') {
+            Some(end) => {
+                let line = &self.rest[..end];
+                (
+                    line.strip_suffix(b"\r").unwrap_or(line),
+                    &self.rest[end + 1..],
+                )
+            }
+            None => (self.rest, &[][..]),
+        };
+
+        self.rest = rest;
+        self.line_number += 1;
+
+        Ok(line)
+    }
+
+    /// Reads a single line, unmodified.
+    ///
+    /// Unlike [`TokenSource::line_raw`], the line is borrowed from the input.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use token_read::SliceReader;
+    /// # use anyhow::Result;
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let mut input = SliceReader::new("1. Write a parsing library in Rust.\n2. ???\n3. Profit!");
+    /// let line: &str = input.line_raw()?;
+    ///
+    /// assert_eq!(line, "1. Write a parsing library in Rust.");
+    /// #
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn line_raw(&mut self) -> Result<&'a str, ReadLineError> {
+        let line = self.line_bytes()?;
+        check_utf8(line, self.line_number)
+    }
+
+    /// Reads a single line and returns an iterator over its tokens, borrowed from the input.
+    ///
+    /// The line is always split at whitespace, regardless of [`ReaderOptions::delimiter`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use token_read::SliceReader;
+    /// # use anyhow::Result;
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let mut input = SliceReader::new("apple banana\ncherry");
+    /// let fruits: Vec<&str> = input.line_tokens()?.collect();
+    ///
+    /// assert_eq!(fruits, vec!["apple", "banana"]);
+    /// #
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn line_tokens(&mut self) -> Result<Tokens<'a>, ReadLineError> {
+        self.line_raw().map(Tokens::new)
+    }
+
+    /// Reads and parses a single line of whitespace delimited tokens.
+    ///
+    /// See [`TokenSource::line`] for details.
+    pub fn line<T>(&mut self) -> Result<T, ReadTokensError<T::Error>>
+    where
+        T: FromTokens,
+    {
+        TokenSource::line(self)
+    }
+
+    /// Reads and parses a single line of whitespace delimited tokens, without requiring it to be valid UTF-8.
+    ///
+    /// See [`TokenSource::byte_line`] for details.
+    pub fn byte_line<T>(&mut self) -> Result<T, ReadTokensError<T::Error>>
+    where
+        T: FromByteTokens,
+    {
+        TokenSource::byte_line(self)
+    }
+
+    /// Creates an iterator that reads and parses a specific number of lines.
+    ///
+    /// See [`TokenSource::take`] for details.
+    pub fn take<T>(&mut self, count: usize) -> Take<'_, T, Self, usize>
+    where
+        T: FromTokens,
+    {
+        Take::new(self, count)
+    }
+
+    /// Like [`SliceReader::take`], but can use non-[`usize`] counts.
+    ///
+    /// See [`TokenSource::take_count`] for details.
+    pub fn take_count<T, S>(&mut self, count: S) -> Take<'_, T, Self, S>
+    where
+        T: FromTokens,
+        S: LineCount,
+    {
+        Take::new(self, count)
+    }
+
+    /// Returns the next line without consuming it.
+    ///
+    /// The line will be returned again by the next read, including [`SliceReader::take`].
+    pub fn peek_line(&mut self) -> Result<&'a str, ReadLineError> {
+        let line = self.fill_pending()?;
+        check_utf8(line, self.line_number + 1)
+    }
+
+    /// Returns the first token of the next line without consuming it, or [`None`] if the line has no tokens.
+    pub fn peek_token(&mut self) -> Result<Option<&str>, ReadLineError> {
+        let line = self.peek_line()?;
+        Ok(self.options.get_delimiter().tokens(line).next())
+    }
+
+    /// Pushes a line back, so that it will be returned by the next read.
+    ///
+    /// Lines are returned in reverse order of unreading. The line number is decreased by one,
+    /// so that it stays correct when the line that was just read is unread.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use token_read::SliceReader;
+    /// # use anyhow::Result;
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let mut input = SliceReader::new("1 2\n3 4");
+    ///
+    /// let line = input.line_raw()?;
+    /// input.unread_line(line);
+    ///
+    /// let values: Vec<(u8, u8)> = input.take(2).collect::<Result<_, _>>()?;
+    /// assert_eq!(values, vec![(1, 2), (3, 4)]);
+    /// #
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn unread_line(&mut self, line: &'a str) {
+        self.pending.push(line.as_bytes());
+        self.line_number = self.line_number.saturating_sub(1);
+    }
+
+    /// Returns the part of the input that hasn't been read yet.
+    ///
+    /// Lines that were peeked at or unread aren't included.
+    pub fn remaining(&self) -> &'a [u8] {
+        self.rest
+    }
+
+    /// Makes sure that the next line has been read into the list of pending lines and returns it.
+    fn fill_pending(&mut self) -> Result<&'a [u8], ReadLineError> {
+        if let Some(&line) = self.pending.last() {
+            return Ok(line);
+        }
+
+        let line = self.line_bytes()?;
+        self.pending.push(line);
+        self.line_number -= 1;
+
+        Ok(line)
+    }
+}
+
+impl TokenSource for SliceReader<'_> {
+    fn with_line<T, F>(&mut self, f: F) -> Result<T, ReadLineError>
+    where
+        F: FnOnce(&[u8], usize) -> T,
+    {
+        let line = self.line_bytes()?;
+        Ok(f(line, self.line_number))
+    }
+
+    /// Splits the line using the delimiter set in the options.
+    fn line<T>(&mut self) -> Result<T, ReadTokensError<T::Error>>
+    where
+        T: FromTokens,
+    {
+        let line = self.line_bytes()?;
+        parse_delimited_line(line, self.line_number, &self.options)
+    }
+}
+
+impl<'a> From<&'a str> for SliceReader<'a> {
+    /// Identical to [`SliceReader::new`].
+    fn from(value: &'a str) -> Self {
+        SliceReader::new(value)
+    }
+}
+
+impl<'a> From<&'a [u8]> for SliceReader<'a> {
+    /// Identical to [`SliceReader::from_bytes`].
+    fn from(value: &'a [u8]) -> Self {
+        SliceReader::from_bytes(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Delimiter, ReadLineError, ReaderOptions, SliceReader};
+
+    #[test]
+    fn borrows_lines_from_input() {
+        let text = String::from("first\nsecond");
+        let mut input = SliceReader::new(&text);
+
+        let first = input.line_raw().unwrap();
+        let second = input.line_raw().unwrap();
+
+        assert_eq!((first, second), ("first", "second"));
+        assert!(std::ptr::eq(first.as_ptr(), text.as_ptr()));
+    }
+
+    #[test]
+    fn returns_borrowed_tokens() {
+        let mut input = SliceReader::new("  a bb\tccc \nx");
+        let tokens: Vec<&str> = input.line_tokens().unwrap().collect();
+
+        assert_eq!(tokens, vec!["a", "bb", "ccc"]);
+        assert_eq!(input.remaining(), b"x");
+    }
+
+    #[test]
+    fn applies_options() {
+        let options = ReaderOptions::new()
+            .skip_blank_lines(true)
+            .comment_prefix("#")
+            .delimiter(Delimiter::chars(","));
+        let mut input = SliceReader::new("# header\n\na b,1\nc").with_options(options);
+
+        assert_eq!(input.peek_token().unwrap(), Some("a b"));
+        assert_eq!(input.line::<(String, u8)>().unwrap(), ("a b".to_owned(), 1));
+        assert_eq!(input.line_number(), 3);
+        assert_eq!(input.line_raw().unwrap(), "c");
+    }
+
+    #[test]
+    fn peeks_and_unreads_lines() {
+        let mut input = SliceReader::new("first\nsecond");
+
+        assert_eq!(input.peek_line().unwrap(), "first");
+        assert_eq!(input.line_number(), 0);
+
+        let first = input.line_raw().unwrap();
+        input.unread_line(first);
+        input.unread_line("zeroth");
+
+        assert_eq!(input.line_raw().unwrap(), "zeroth");
+        assert_eq!(input.line_raw().unwrap(), "first");
+        assert_eq!(input.line_raw().unwrap(), "second");
+        assert_eq!(input.line_number(), 3);
+        assert!(matches!(input.peek_line(), Err(ReadLineError::EndOfFile)));
+    }
+}
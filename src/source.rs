@@ -0,0 +1,453 @@
+use core::str;
+
+use alloc::{borrow::ToOwned, string::String};
+
+use crate::{
+    ByteTokens, FromByteTokens, FromTokens, LineCount, ReadLineError, ReadTokensError,
+    ReaderOptions, Remainder, Take, Tokens,
+};
+
+#[cfg(feature = "std")]
+use std::num::ParseIntError;
+
+#[cfg(feature = "std")]
+use crate::graph::{
+    self, EdgeList, EdgeListOptions, EdgeTokens, ReadGraphError, ReadTreeError, Tree,
+};
+#[cfg(feature = "serde")]
+use crate::{de, DeserializeError};
+#[cfg(feature = "serde")]
+use serde::de::DeserializeOwned;
+
+#[cfg(doc)]
+use crate::SliceReader;
+#[cfg(all(doc, feature = "std"))]
+use crate::TokenReader;
+
+/// A trait for backends that provide lines of whitespace delimited tokens.
+///
+#[cfg_attr(
+    feature = "std",
+    doc = "It's implemented by [`TokenReader`], which reads from a [`BufRead`](std::io::BufRead),"
+)]
+#[cfg_attr(
+    not(feature = "std"),
+    doc = "It's implemented by `TokenReader`, which reads from a `BufRead` and requires the `std` feature,"
+)]
+/// and by [`SliceReader`], which reads from a string or a byte slice that's already in memory.
+/// Code generic over this trait can be switched to another backend by changing only the constructor.
+///
+/// # Example
+///
+/// ```
+/// # use token_read::{SliceReader, TokenSource};
+/// # use anyhow::Result;
+/// #
+/// fn sum<S: TokenSource>(input: &mut S) -> Result<u64> {
+///     let (count,): (usize,) = input.line()?;
+///     let values: Vec<(u64,)> = input.take(count).collect::<Result<_, _>>()?;
+///
+///     Ok(values.into_iter().map(|(value,)| value).sum())
+/// }
+///
+/// # fn main() -> Result<()> {
+/// # #[cfg(feature = "std")]
+/// assert_eq!(sum(&mut token_read::TokenReader::new("2\n3\n4".as_bytes()))?, 7);
+/// assert_eq!(sum(&mut SliceReader::new("2\n3\n4"))?, 7);
+/// #
+/// #   Ok(())
+/// # }
+/// ```
+pub trait TokenSource {
+    /// Reads the next line and processes it using a function, which also receives the line number.
+    ///
+    /// The line terminator, either `\n` or `\r\n`, is not included.
+    /// This is the hook used by the other methods, it's not meant to be called directly.
+    #[doc(hidden)]
+    fn with_line<T, F>(&mut self, f: F) -> Result<T, ReadLineError>
+    where
+        F: FnOnce(&[u8], usize) -> T;
+
+    /// Reads and parses a single line of whitespace delimited tokens.
+    ///
+    #[cfg_attr(feature = "std", doc = "See [`TokenReader::line`] for details.")]
+    fn line<T>(&mut self) -> Result<T, ReadTokensError<T::Error>>
+    where
+        T: FromTokens,
+    {
+        self.with_line(parse_line)?
+    }
+
+    /// Reads a single line, unmodified.
+    ///
+    #[cfg_attr(feature = "std", doc = "See [`TokenReader::line_raw`] for details.")]
+    fn line_raw(&mut self) -> Result<String, ReadLineError> {
+        self.with_line(owned_line)?
+    }
+
+    /// Reads and parses a single line of whitespace delimited tokens, without requiring it to be valid UTF-8.
+    ///
+    #[cfg_attr(feature = "std", doc = "See [`TokenReader::byte_line`] for details.")]
+    fn byte_line<T>(&mut self) -> Result<T, ReadTokensError<T::Error>>
+    where
+        T: FromByteTokens,
+    {
+        self.with_line(parse_byte_line)?
+    }
+
+    /// Creates an iterator that reads and parses a specific number of lines.
+    ///
+    #[cfg_attr(feature = "std", doc = "See [`TokenReader::take`] for details.")]
+    fn take<T>(&mut self, count: usize) -> Take<'_, T, Self, usize>
+    where
+        Self: Sized,
+        T: FromTokens,
+    {
+        Take::new(self, count)
+    }
+
+    /// Like [`TokenSource::take`], but can use non-[`usize`] counts.
+    ///
+    #[cfg_attr(feature = "std", doc = "See [`TokenReader::take_count`] for details.")]
+    fn take_count<T, S>(&mut self, count: S) -> Take<'_, T, Self, S>
+    where
+        Self: Sized,
+        T: FromTokens,
+        S: LineCount,
+    {
+        Take::new(self, count)
+    }
+
+    /// Reads a graph given as a line containing the vertex and edge count, followed by the edges.
+    ///
+    /// Every edge is read from a single line as the type `E`,
+    /// which is either `(usize, usize)` for unweighted graphs or `(usize, usize, W)` for weighted ones.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use token_read::{TokenReader, TokenSource};
+    /// # use token_read::graph::EdgeListOptions;
+    /// # use anyhow::Result;
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let mut input = TokenReader::new("3 2\n1 2\n3 2".as_bytes());
+    /// let graph = input.graph::<(usize, usize)>(EdgeListOptions::new().directed(true))?;
+    ///
+    /// assert_eq!(graph.neighbours(), vec![vec![1], vec![], vec![1]]);
+    /// #
+    /// #   Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "std")]
+    fn graph<E>(
+        &mut self,
+        options: EdgeListOptions,
+    ) -> Result<EdgeList<E::Weight>, ReadGraphError<E::Error>>
+    where
+        Self: Sized,
+        E: EdgeTokens,
+    {
+        graph::read_graph::<_, E>(self, options)
+    }
+
+    /// Reads a specific number of edges of a graph with a known number of vertices.
+    ///
+    /// This is useful when the vertex and edge counts aren't on their own line.
+    /// See [`TokenSource::graph`] for details.
+    #[cfg(feature = "std")]
+    fn edges<E>(
+        &mut self,
+        vertex_count: usize,
+        edge_count: usize,
+        options: EdgeListOptions,
+    ) -> Result<EdgeList<E::Weight>, ReadGraphError<E::Error>>
+    where
+        Self: Sized,
+        E: EdgeTokens,
+    {
+        graph::read_edges::<_, E>(self, vertex_count, edge_count, options)
+    }
+
+    /// Reads a tree given as a single line containing the parents of vertices `2` to `n`.
+    ///
+    /// The vertices are numbered from one, with vertex `1` being the root.
+    /// In the returned tree, they are converted to zero-based indices.
+    /// An error is returned if the parents don't form a tree or if the vertex count is zero,
+    /// in which case no line is read.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use token_read::{TokenReader, TokenSource};
+    /// # use anyhow::Result;
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let mut input = TokenReader::new("1 1 2".as_bytes());
+    /// let tree = input.parent_tree(4)?;
+    ///
+    /// assert_eq!(tree.children(0), [1, 2]);
+    /// assert_eq!(tree.parent(3), Some(1));
+    /// #
+    /// #   Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "std")]
+    fn parent_tree(&mut self, vertex_count: usize) -> Result<Tree, ReadTreeError<ParseIntError>>
+    where
+        Self: Sized,
+    {
+        graph::read_parent_tree(self, vertex_count)
+    }
+
+    /// Reads a tree given as a list of `n - 1` edges and roots it at the given zero-based vertex.
+    ///
+    /// The edges are read the same way as in [`TokenSource::edges`],
+    /// except that the graph is always treated as undirected.
+    /// An error is returned if the edges don't form a tree or if the vertex count is zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the tree isn't empty and the root is out of range.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use token_read::{TokenReader, TokenSource};
+    /// # use token_read::graph::EdgeListOptions;
+    /// # use anyhow::Result;
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let mut input = TokenReader::new("1 2 5\n3 1 7".as_bytes());
+    /// let tree = input.tree::<(usize, usize, u32)>(3, 0, EdgeListOptions::new())?;
+    ///
+    /// assert_eq!(tree.children(0), [1, 2]);
+    /// assert_eq!(tree.parent_weight(2), Some(&7));
+    /// #
+    /// #   Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "std")]
+    fn tree<E>(
+        &mut self,
+        vertex_count: usize,
+        root: usize,
+        options: EdgeListOptions,
+    ) -> Result<Tree<E::Weight>, ReadTreeError<E::Error>>
+    where
+        Self: Sized,
+        E: EdgeTokens,
+    {
+        graph::read_tree::<_, E>(self, vertex_count, root, options)
+    }
+
+    /// Deserializes a value using [serde](https://serde.rs), reading as many lines as needed.
+    ///
+    /// The value must end at the end of a line.
+    /// See [`Deserializer`](crate::Deserializer) for details on how values are mapped to tokens.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use serde::Deserialize;
+    /// # use token_read::{SliceReader, TokenSource};
+    /// # use anyhow::Result;
+    /// #
+    /// #[derive(Deserialize)]
+    /// enum Query {
+    ///     #[serde(rename = "add")]
+    ///     Add(u32, u32),
+    ///     #[serde(rename = "print")]
+    ///     Print,
+    /// }
+    ///
+    /// # fn main() -> Result<()> {
+    /// let mut input = SliceReader::new("add 1 2\nprint");
+    ///
+    /// assert!(matches!(input.deserialize()?, Query::Add(1, 2)));
+    /// assert!(matches!(input.deserialize()?, Query::Print));
+    /// #
+    /// #   Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "serde")]
+    fn deserialize<T>(&mut self) -> Result<T, DeserializeError>
+    where
+        Self: Sized,
+        T: DeserializeOwned,
+    {
+        de::deserialize_lines(self)
+    }
+}
+
+/// Checks that a line is valid UTF-8.
+pub(crate) fn check_utf8(line: &[u8], number: usize) -> Result<&str, ReadLineError> {
+    str::from_utf8(line).map_err(|error| ReadLineError::InvalidUtf8 {
+        line: number,
+        offset: error.valid_up_to(),
+    })
+}
+
+/// Parses a line of whitespace delimited tokens, checking that it's valid UTF-8.
+pub(crate) fn parse_line<T>(line: &[u8], number: usize) -> Result<T, ReadTokensError<T::Error>>
+where
+    T: FromTokens,
+{
+    let line = check_utf8(line, number)?;
+    let tokens = Tokens::new(line);
+
+    T::from_line_tokens(tokens).map_err(|source| ReadTokensError::ParseError {
+        source,
+        line: line.to_owned(),
+    })
+}
+
+/// Parses a line of tokens as configured by the options, checking that it's valid UTF-8.
+pub(crate) fn parse_delimited_line<T>(
+    line: &[u8],
+    number: usize,
+    options: &ReaderOptions,
+) -> Result<T, ReadTokensError<T::Error>>
+where
+    T: FromTokens,
+{
+    let line = check_utf8(line, number)?;
+    let tokens = InRadix {
+        tokens: options.get_delimiter().tokens(line),
+        radix: options.get_integer_radix(),
+    };
+
+    T::from_line_tokens(tokens).map_err(|source| ReadTokensError::ParseError {
+        source,
+        line: line.to_owned(),
+    })
+}
+
+/// Tokens of a line whose primitive integers are written in the given radix.
+struct InRadix<I> {
+    tokens: I,
+    radix: u32,
+}
+
+impl<'a, I> Iterator for InRadix<I>
+where
+    I: Iterator<Item = &'a str>,
+{
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.tokens.next()
+    }
+}
+
+impl<'a, I> Remainder<'a> for InRadix<I>
+where
+    I: Remainder<'a>,
+{
+    fn remainder(&mut self) -> &'a str {
+        self.tokens.remainder()
+    }
+
+    fn integer_radix(&self) -> u32 {
+        self.radix
+    }
+}
+
+/// Parses a line of whitespace delimited tokens, without requiring it to be valid UTF-8.
+pub(crate) fn parse_byte_line<T>(line: &[u8], _: usize) -> Result<T, ReadTokensError<T::Error>>
+where
+    T: FromByteTokens,
+{
+    let tokens = ByteTokens::new(line);
+
+    T::from_byte_tokens(tokens).map_err(|source| ReadTokensError::ParseError {
+        source,
+        line: String::from_utf8_lossy(line).into_owned(),
+    })
+}
+
+/// Copies a line into a [`String`], checking that it's valid UTF-8.
+pub(crate) fn owned_line(line: &[u8], number: usize) -> Result<String, ReadLineError> {
+    check_utf8(line, number).map(str::to_owned)
+}
+
+/// Forwards every method, so that readers overriding them behave the same way through a reference.
+///
+/// [`TokenSource::take`] and [`TokenSource::take_count`] return an iterator borrowing the reference,
+/// which reads lines using the forwarded [`TokenSource::line`].
+impl<S: TokenSource> TokenSource for &mut S {
+    fn with_line<T, F>(&mut self, f: F) -> Result<T, ReadLineError>
+    where
+        F: FnOnce(&[u8], usize) -> T,
+    {
+        (**self).with_line(f)
+    }
+
+    fn line<T>(&mut self) -> Result<T, ReadTokensError<T::Error>>
+    where
+        T: FromTokens,
+    {
+        (**self).line()
+    }
+
+    fn line_raw(&mut self) -> Result<String, ReadLineError> {
+        (**self).line_raw()
+    }
+
+    fn byte_line<T>(&mut self) -> Result<T, ReadTokensError<T::Error>>
+    where
+        T: FromByteTokens,
+    {
+        (**self).byte_line()
+    }
+
+    #[cfg(feature = "std")]
+    fn graph<E>(
+        &mut self,
+        options: EdgeListOptions,
+    ) -> Result<EdgeList<E::Weight>, ReadGraphError<E::Error>>
+    where
+        E: EdgeTokens,
+    {
+        (**self).graph::<E>(options)
+    }
+
+    #[cfg(feature = "std")]
+    fn edges<E>(
+        &mut self,
+        vertex_count: usize,
+        edge_count: usize,
+        options: EdgeListOptions,
+    ) -> Result<EdgeList<E::Weight>, ReadGraphError<E::Error>>
+    where
+        E: EdgeTokens,
+    {
+        (**self).edges::<E>(vertex_count, edge_count, options)
+    }
+
+    #[cfg(feature = "std")]
+    fn parent_tree(&mut self, vertex_count: usize) -> Result<Tree, ReadTreeError<ParseIntError>> {
+        (**self).parent_tree(vertex_count)
+    }
+
+    #[cfg(feature = "std")]
+    fn tree<E>(
+        &mut self,
+        vertex_count: usize,
+        root: usize,
+        options: EdgeListOptions,
+    ) -> Result<Tree<E::Weight>, ReadTreeError<E::Error>>
+    where
+        E: EdgeTokens,
+    {
+        (**self).tree::<E>(vertex_count, root, options)
+    }
+
+    #[cfg(feature = "serde")]
+    fn deserialize<T>(&mut self) -> Result<T, DeserializeError>
+    where
+        T: DeserializeOwned,
+    {
+        (**self).deserialize()
+    }
+}
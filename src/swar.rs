@@ -0,0 +1,144 @@
+//! Helpers processing eight bytes at once using ordinary 64-bit arithmetic.
+
+use crate::bytes::is_whitespace;
+
+const ONES: u64 = 0x0101_0101_0101_0101;
+const HIGH_BITS: u64 = 0x8080_8080_8080_8080;
+
+fn load(chunk: &[u8]) -> u64 {
+    u64::from_le_bytes(chunk.try_into().expect("chunk should have eight bytes"))
+}
+
+/// Finds the index of the first ASCII whitespace byte.
+pub(crate) fn find_whitespace(bytes: &[u8]) -> Option<usize> {
+    let mut chunks = bytes.chunks_exact(8);
+    let mut offset = 0;
+
+    for chunk in &mut chunks {
+        let word = load(chunk);
+
+        // Marks every byte smaller than 0x21. Borrows can cause false positives,
+        // but only above a byte that is really marked, so the candidates are checked one by one.
+        let mut candidates = word.wrapping_sub(ONES * 0x21) & !word & HIGH_BITS;
+
+        while candidates != 0 {
+            let index = candidates.trailing_zeros() as usize / 8;
+
+            if is_whitespace(chunk[index]) {
+                return Some(offset + index);
+            }
+
+            candidates &= candidates - 1;
+        }
+
+        offset += 8;
+    }
+
+    chunks
+        .remainder()
+        .iter()
+        .position(|&b| is_whitespace(b))
+        .map(|index| offset + index)
+}
+
+/// Parses eight ASCII digits stored in little-endian order.
+fn parse_eight_digits(word: u64) -> Option<u64> {
+    let upper = word & 0xf0f0_f0f0_f0f0_f0f0;
+    let upper_plus_six = (word.wrapping_add(ONES * 6) & 0xf0f0_f0f0_f0f0_f0f0) >> 4;
+
+    if upper | upper_plus_six != ONES * 0x33 {
+        return None;
+    }
+
+    let word = word - ONES * b'0' as u64;
+    let word = word * 10 + (word >> 8);
+    let word = (word & 0x0000_00ff_0000_00ff).wrapping_mul(100 + (1_000_000 << 32))
+        + ((word >> 16) & 0x0000_00ff_0000_00ff).wrapping_mul(1 + (10_000 << 32));
+
+    Some(word >> 32)
+}
+
+/// Parses at most 19 ASCII digits, returning [`None`] if any byte is not a digit.
+pub(crate) fn parse_digits(digits: &[u8]) -> Option<u64> {
+    debug_assert!(digits.len() <= 19);
+
+    let mut chunks = digits.chunks_exact(8);
+    let mut value = 0;
+
+    for chunk in &mut chunks {
+        value = value * 100_000_000 + parse_eight_digits(load(chunk))?;
+    }
+
+    for &digit in chunks.remainder() {
+        if !digit.is_ascii_digit() {
+            return None;
+        }
+
+        value = value * 10 + u64::from(digit - b'0');
+    }
+
+    Some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::{find_whitespace, parse_digits};
+    use crate::bytes::is_whitespace;
+
+    #[test]
+    fn finds_whitespace_in_any_position() {
+        for length in 0..20 {
+            for position in 0..length {
+                for whitespace in [b' ', b'\t', b'\n', b'\x0b', b'\x0c', b'\r'] {
+                    let mut bytes = vec![b'7'; length];
+                    bytes[position] = whitespace;
+                    assert_eq!(find_whitespace(&bytes), Some(position));
+                }
+            }
+
+            assert_eq!(find_whitespace(&vec![b'7'; length]), None);
+        }
+    }
+
+    #[test]
+    fn ignores_other_control_characters() {
+        let bytes = b"\x00\x01\x08\x0e\x1f\x21\x7f\xff \xa0";
+        assert_eq!(find_whitespace(bytes), Some(8));
+    }
+
+    #[test]
+    fn parses_digits() {
+        assert_eq!(parse_digits(b""), Some(0));
+        assert_eq!(parse_digits(b"12345678"), Some(12345678));
+        assert_eq!(
+            parse_digits(b"9999999999999999999"),
+            Some(9999999999999999999)
+        );
+        assert_eq!(parse_digits(b"0000000000000000001"), Some(1));
+        assert_eq!(parse_digits(b"1234567/"), None);
+        assert_eq!(parse_digits(b"1234567:"), None);
+        assert_eq!(parse_digits(b"\xff2345678"), None);
+    }
+
+    proptest! {
+        #[test]
+        fn finds_whitespace_like_scalar(bytes: Vec<u8>) {
+            let expected = bytes.iter().position(|&b| is_whitespace(b));
+            prop_assert_eq!(find_whitespace(&bytes), expected);
+        }
+
+        #[test]
+        fn parses_digits_like_scalar(digits in "[0-9]{0,19}") {
+            prop_assert_eq!(parse_digits(digits.as_bytes()), digits.parse().ok().or(Some(0)));
+        }
+
+        #[test]
+        fn rejects_non_digits(digits in "[0-9]{0,8}[^0-9][0-9]{0,8}") {
+            let digits = &digits.as_bytes()[..digits.len().min(19)];
+            let expected = digits.iter().all(u8::is_ascii_digit);
+            prop_assert_eq!(parse_digits(digits).is_some(), expected);
+        }
+    }
+}
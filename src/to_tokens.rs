@@ -1,6 +1,9 @@
-use std::str::SplitWhitespace;
+use core::mem;
 
-use crate::FromTokens;
+use crate::{bytes::is_whitespace, swar::find_whitespace, FromTokens};
+
+#[cfg(doc)]
+use crate::{ReaderOptions, RestOfLine, TokenSource};
 
 /// A trait for types that can be used to create an iterator of tokens.
 ///
@@ -37,17 +40,131 @@ pub trait ToTokens<'a> {
 }
 
 impl<'a> ToTokens<'a> for str {
-    type Iter = SplitWhitespace<'a>;
+    type Iter = Tokens<'a>;
 
     /// Splits the string by any whitespace (including newlines).
     fn to_tokens(&'a self) -> Self::Iter {
-        self.split_whitespace()
+        Tokens::new(self)
+    }
+
+    fn parse_tokens<T>(&'a self) -> Result<T, T::Error>
+    where
+        T: FromTokens,
+    {
+        T::from_line_tokens(self.to_tokens())
+    }
+}
+
+/// An iterator of tokens which can also return the part of the line that wasn't split yet.
+///
+/// It's used by [`RestOfLine`] to keep the original spacing of the end of the line.
+pub trait Remainder<'a>: Iterator<Item = &'a str> {
+    /// Returns the rest of the line after the last returned token, without leading and trailing whitespace.
+    ///
+    /// The iterator returns no more tokens afterwards.
+    fn remainder(&mut self) -> &'a str;
+
+    /// Returns the radix primitive integer tokens are written in, as set by [`ReaderOptions::integer_radix`].
+    ///
+    /// The default implementation returns 10.
+    fn integer_radix(&self) -> u32 {
+        10
+    }
+}
+
+/// An iterator over the whitespace delimited tokens of a string.
+///
+/// It's used by [`TokenSource::line`] and [`ToTokens::to_tokens`].
+/// It produces the same tokens as [`str::split_whitespace`],
+/// but searches for the end of ASCII tokens eight bytes at a time.
+#[derive(Debug, Clone)]
+pub struct Tokens<'a> {
+    inner: TokensInner<'a>,
+}
+
+#[derive(Debug, Clone)]
+enum TokensInner<'a> {
+    Ascii(&'a str),
+    Unicode(&'a str),
+}
+
+impl<'a> Tokens<'a> {
+    /// Creates an iterator over the tokens of a string.
+    pub fn new(string: &'a str) -> Self {
+        // Strings containing non-ASCII characters may contain Unicode whitespace.
+        let inner = if string.is_ascii() {
+            TokensInner::Ascii(string)
+        } else {
+            TokensInner::Unicode(string)
+        };
+
+        Tokens { inner }
+    }
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.inner {
+            TokensInner::Ascii(rest) => {
+                let bytes = rest.as_bytes();
+
+                let Some(start) = bytes.iter().position(|&b| !is_whitespace(b)) else {
+                    *rest = "";
+                    return None;
+                };
+
+                let end = find_whitespace(&bytes[start..]).map_or(bytes.len(), |end| start + end);
+                let token = &rest[start..end];
+
+                *rest = &rest[end..];
+                Some(token)
+            }
+            TokensInner::Unicode(rest) => {
+                let string = rest.trim_start();
+
+                if string.is_empty() {
+                    *rest = "";
+                    return None;
+                }
+
+                let end = string.find(char::is_whitespace).unwrap_or(string.len());
+                let (token, after) = string.split_at(end);
+
+                *rest = after;
+                Some(token)
+            }
+        }
+    }
+}
+
+impl<'a> Remainder<'a> for Tokens<'a> {
+    fn remainder(&mut self) -> &'a str {
+        match &mut self.inner {
+            TokensInner::Ascii(rest) | TokensInner::Unicode(rest) => mem::take(rest).trim(),
+        }
+    }
+}
+
+impl<'a, R> Remainder<'a> for &mut R
+where
+    R: Remainder<'a> + ?Sized,
+{
+    fn remainder(&mut self) -> &'a str {
+        (**self).remainder()
+    }
+
+    fn integer_radix(&self) -> u32 {
+        (**self).integer_radix()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::ToTokens;
+    use proptest::prelude::*;
+
+    use crate::{Remainder, ToTokens, Tokens};
 
     #[test]
     fn splits_string_at_whitespace() {
@@ -61,4 +178,42 @@ mod tests {
         let numbers: Vec<u8> = "1 2".parse_tokens().unwrap();
         assert_eq!(numbers, vec![1, 2]);
     }
+
+    #[test]
+    fn splits_unicode_whitespace() {
+        let tokens: Vec<&str> = "a\u{a0}b\u{3000}č\u{2028}".to_tokens().collect();
+        assert_eq!(tokens, vec!["a", "b", "č"]);
+    }
+
+    #[test]
+    fn returns_remainder() {
+        let mut tokens = Tokens::new(" a  b\u{a0} c ");
+
+        assert_eq!(tokens.next(), Some("a"));
+        assert_eq!(tokens.remainder(), "b\u{a0} c");
+        assert_eq!(tokens.next(), None);
+    }
+
+    #[test]
+    fn splits_long_tokens() {
+        let input = "0123456789abcdefghij \t0123456789abcdefghij0123456789\r\n";
+        let tokens: Vec<&str> = input.to_tokens().collect();
+        assert_eq!(tokens, input.split_whitespace().collect::<Vec<_>>());
+    }
+
+    proptest! {
+        #[test]
+        fn splits_ascii_like_split_whitespace(input in "[ \t\r\n\x0b\x0c\x00\x1fa-z0-9]{0,64}") {
+            let real: Vec<&str> = input.to_tokens().collect();
+            let expected: Vec<&str> = input.split_whitespace().collect();
+            prop_assert_eq!(real, expected);
+        }
+
+        #[test]
+        fn splits_any_string_like_split_whitespace(input: String) {
+            let real: Vec<&str> = input.to_tokens().collect();
+            let expected: Vec<&str> = input.split_whitespace().collect();
+            prop_assert_eq!(real, expected);
+        }
+    }
 }
@@ -0,0 +1,105 @@
+//! Asynchronous reading from types implementing [`AsyncBufRead`](::tokio::io::AsyncBufRead) from [tokio](https://crates.io/crates/tokio).
+//!
+//! This module is only available with the `tokio` feature.
+//!
+//! # Example
+//!
+//! ```
+//! # use anyhow::Result;
+//! use futures::StreamExt;
+//! use tokio::io::{duplex, AsyncWriteExt, BufReader};
+//! use token_read::tokio::AsyncTokenReader;
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<()> {
+//! let (mut client, server) = duplex(64);
+//! client.write_all(b"2\n1 a\n2 b\n").await?;
+//!
+//! let mut input = AsyncTokenReader::new(BufReader::new(server));
+//! let (count,): (usize,) = input.line().await?;
+//! let lines: Vec<(u8, char)> = input
+//!     .take::<(u8, char)>(count)
+//!     .map(Result::unwrap)
+//!     .collect()
+//!     .await;
+//!
+//! assert_eq!(lines, vec![(1, 'a'), (2, 'b')]);
+//! #
+//! #   Ok(())
+//! # }
+//! ```
+
+async_reader!(::tokio::io::AsyncBufRead);
+
+#[cfg(test)]
+mod tests {
+    use futures::{FutureExt, StreamExt};
+    use tokio::io::{duplex, AsyncWriteExt, BufReader};
+
+    use super::AsyncTokenReader;
+    use crate::{ReadLineError, ReadTokensError};
+
+    #[tokio::test]
+    async fn reads_lines_from_duplex_stream() {
+        let (mut client, server) = duplex(64);
+        let mut input = AsyncTokenReader::new(BufReader::new(server));
+
+        client.write_all(b"First\r\n1 2 3\nlast").await.unwrap();
+        drop(client);
+
+        assert_eq!(input.line_raw().await.unwrap(), "First");
+        assert_eq!(input.line::<Vec<u8>>().await.unwrap(), vec![1, 2, 3]);
+        assert_eq!(input.line_raw().await.unwrap(), "last");
+        assert!(matches!(
+            input.line_raw().await,
+            Err(ReadLineError::EndOfFile)
+        ));
+    }
+
+    #[tokio::test]
+    async fn reads_lines_split_across_writes() {
+        let (mut client, server) = duplex(4);
+        let mut input = AsyncTokenReader::new(BufReader::with_capacity(4, server));
+
+        let writer = tokio::spawn(async move {
+            client.write_all(b"10 20 30 40\r\n\xff 5\n").await.unwrap();
+        });
+
+        let values: Vec<u32> = input.line().await.unwrap();
+        let (bytes, value): (Vec<u8>, u8) = input.byte_line().await.unwrap();
+
+        assert_eq!(values, vec![10, 20, 30, 40]);
+        assert_eq!((bytes, value), (vec![0xff], 5));
+
+        writer.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn keeps_partial_line_when_cancelled() {
+        let (mut client, server) = duplex(64);
+        let mut input = AsyncTokenReader::new(BufReader::new(server));
+
+        client.write_all(b"12 3").await.unwrap();
+        assert!(input.line::<Vec<u8>>().now_or_never().is_none());
+
+        client.write_all(b"4\n").await.unwrap();
+        assert_eq!(input.line::<Vec<u8>>().await.unwrap(), vec![12, 34]);
+    }
+
+    #[tokio::test]
+    async fn take_streams_lines() {
+        let (mut client, server) = duplex(64);
+        let mut input = AsyncTokenReader::new(BufReader::new(server));
+
+        client.write_all(b"1\n2\nx\n4").await.unwrap();
+        drop(client);
+
+        let lines: Vec<_> = input.take_count::<(u8,), _>(3u8).collect().await;
+
+        assert!(matches!(lines[..2], [Ok((1,)), Ok((2,))]));
+        assert!(
+            matches!(lines[2], Err(ReadTokensError::ParseError { ref line, .. }) if line == "x")
+        );
+        assert_eq!(input.line_raw().await.unwrap(), "4");
+    }
+}